@@ -0,0 +1,112 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+// known-correct answers are stored in `answers/dayN.txt`, with the answer to
+// part 1 on the first line and the one to part 2 on the second. an empty or
+// missing line means that no answer has been recorded for that part yet.
+const DIR: &str = "answers";
+
+fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}.txt"))
+}
+
+pub fn load(day: u8) -> anyhow::Result<[Option<String>; 2]> {
+    load_from(Path::new(DIR), day)
+}
+
+// records the answers that are known now. parts without one keep whatever was
+// recorded for them before, so e.g. recording a day with only part 1 solved
+// doesn't lose an older answer to part 2.
+pub fn record(day: u8, answers: [Option<String>; 2]) -> anyhow::Result<()> {
+    record_in(Path::new(DIR), day, answers)
+}
+
+fn load_from(dir: &Path, day: u8) -> anyhow::Result<[Option<String>; 2]> {
+    let path = path(dir, day);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok([None, None]),
+        Err(e) => return Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+    };
+
+    let mut lines = text
+        .lines()
+        .map(|l| Some(l.trim()).filter(|l| !l.is_empty()));
+    Ok([(); 2].map(|()| lines.next().flatten().map(str::to_string)))
+}
+
+fn store_in(dir: &Path, day: u8, answers: &[Option<String>; 2]) -> anyhow::Result<()> {
+    if answers.iter().all(Option::is_none) {
+        return Ok(());
+    }
+    if let Some(a) = answers.iter().flatten().find(|a| a.contains('\n')) {
+        anyhow::bail!("Can't record multi-line answer {a:?} for day {day}");
+    }
+
+    let path = path(dir, day);
+    fs::create_dir_all(dir).with_context(|| format!("Couldn't create {}", dir.display()))?;
+    let [p1, p2] = answers.each_ref().map(|a| a.as_deref().unwrap_or(""));
+    fs::write(&path, format!("{p1}\n{p2}\n"))
+        .with_context(|| format!("Couldn't write {}", path.display()))
+}
+
+fn record_in(dir: &Path, day: u8, answers: [Option<String>; 2]) -> anyhow::Result<()> {
+    let mut merged = load_from(dir, day)?;
+    for (old, new) in merged.iter_mut().zip(answers) {
+        if new.is_some() {
+            *old = new;
+        }
+    }
+    store_in(dir, day, &merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a fresh directory per test, since they run in parallel.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-answers-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn some(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = temp_dir("round_trip");
+        assert_eq!(load_from(&dir, 1).unwrap(), [None, None]);
+
+        store_in(&dir, 1, &[some("11"), some("31")]).unwrap();
+        assert_eq!(load_from(&dir, 1).unwrap(), [some("11"), some("31")]);
+        // only part 2 is known, so part 1's line stays empty.
+        store_in(&dir, 2, &[None, some("4")]).unwrap();
+        assert_eq!(load_from(&dir, 2).unwrap(), [None, some("4")]);
+        store_in(&dir, 3, &[some("2"), None]).unwrap();
+        assert_eq!(load_from(&dir, 3).unwrap(), [some("2"), None]);
+
+        assert!(store_in(&dir, 4, &[some("a\nb"), None]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn record_merges() {
+        let dir = temp_dir("record_merges");
+        record_in(&dir, 5, [some("143"), None]).unwrap();
+        record_in(&dir, 5, [None, some("123")]).unwrap();
+        assert_eq!(load_from(&dir, 5).unwrap(), [some("143"), some("123")]);
+
+        // newer answers replace older ones, and recording nothing changes nothing.
+        record_in(&dir, 5, [some("144"), None]).unwrap();
+        record_in(&dir, 5, [None, None]).unwrap();
+        assert_eq!(load_from(&dir, 5).unwrap(), [some("144"), some("123")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
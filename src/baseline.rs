@@ -0,0 +1,292 @@
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::bench::{Stats, StatsNs};
+
+// saved timings live in `baselines/<name>.json`.
+const DIR: &str = "baselines";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn part(part: u8) -> Phase {
+        match part {
+            1 => Phase::Part1,
+            2 => Phase::Part2,
+            _ => panic!("there is no part {part}"),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Parse => "Parsing",
+            Phase::Part1 => "Part 1",
+            Phase::Part2 => "Part 2",
+        })
+    }
+}
+
+// the statistics of a single phase of a single day.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub phase: Phase,
+    #[serde(flatten)]
+    pub stats: StatsNs,
+}
+
+impl Timing {
+    pub fn new(day: u8, phase: Phase, stats: &Stats) -> Timing {
+        Timing {
+            day,
+            phase,
+            stats: stats.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Machine {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub cpu_model: Option<String>,
+    pub hostname: Option<String>,
+}
+
+impl Machine {
+    pub fn current() -> Machine {
+        // best effort, these files only exist on linux.
+        let cpu_model = fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+            info.lines()
+                .find_map(|l| l.strip_prefix("model name")?.split_once(':'))
+                .map(|(_, model)| model.trim().to_string())
+        });
+        let hostname = fs::read_to_string("/etc/hostname")
+            .ok()
+            .map(|h| h.trim().to_string());
+
+        Machine {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            cpu_model,
+            hostname,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    pub name: String,
+    // seconds since the unix epoch.
+    pub created: u64,
+    pub machine: Machine,
+    pub iterations: u32,
+    pub timings: Vec<Timing>,
+}
+
+impl Baseline {
+    pub fn new(name: &str, iterations: u32, timings: Vec<Timing>) -> Baseline {
+        Baseline {
+            name: name.to_string(),
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            machine: Machine::current(),
+            iterations,
+            timings,
+        }
+    }
+
+    fn path(name: &str) -> anyhow::Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            anyhow::bail!("Invalid baseline name {name:?}");
+        }
+        Ok(PathBuf::from(format!("{DIR}/{name}.json")))
+    }
+
+    pub fn load(name: &str) -> anyhow::Result<Baseline> {
+        let path = Self::path(name)?;
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Couldn't read baseline {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Couldn't parse baseline {}", path.display()))
+    }
+
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let path = Self::path(&self.name)?;
+        fs::create_dir_all(DIR).with_context(|| format!("Couldn't create {DIR}/"))?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Couldn't write baseline {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: Phase,
+    pub old_ns: f64,
+    pub new_ns: f64,
+    // welch's t statistic of the two means. with a few dozen samples, anything
+    // beyond ~2 is unlikely to be noise. it's 0 if either side has too few samples
+    // or no variance at all, since then nothing can be told apart from noise.
+    pub t: f64,
+}
+
+impl Comparison {
+    // relative change of the mean, e.g. 0.1 for 10% slower.
+    pub fn change(&self) -> f64 {
+        self.new_ns / self.old_ns - 1.0
+    }
+
+    pub fn significant(&self) -> bool {
+        self.t.abs() > 2.0
+    }
+
+    // `threshold` is the tolerated relative slowdown, e.g. 0.05 for 5%.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.significant() && self.change() > threshold
+    }
+
+    // `*`, `**` or `***` for roughly 95%, 99% and 99.9% confidence.
+    pub fn marker(&self) -> &'static str {
+        match self.t.abs() {
+            t if t > 3.3 => "***",
+            t if t > 2.6 => "**",
+            t if t > 2.0 => "*",
+            _ => "",
+        }
+    }
+}
+
+// compares all timings that exist in both the baseline and `new`.
+pub fn compare(old: &Baseline, new: &[Timing]) -> Vec<Comparison> {
+    new.iter()
+        .filter_map(|n| {
+            let o = old
+                .timings
+                .iter()
+                .find(|o| (o.day, o.phase) == (n.day, n.phase))?;
+            let se = (o.stats.stddev_ns.powi(2) / o.stats.samples as f64
+                + n.stats.stddev_ns.powi(2) / n.stats.samples as f64)
+                .sqrt();
+            let diff = n.stats.mean_ns - o.stats.mean_ns;
+            // a single sample has no variance to speak of, and without any variance
+            // there's no telling a real difference from noise, so neither counts.
+            let t = if o.stats.samples < 2 || n.stats.samples < 2 || se == 0.0 {
+                0.0
+            } else {
+                diff / se
+            };
+
+            Some(Comparison {
+                day: n.day,
+                phase: n.phase,
+                old_ns: o.stats.mean_ns,
+                new_ns: n.stats.mean_ns,
+                t,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, phase: Phase, mean_ns: f64, stddev_ns: f64) -> Timing {
+        Timing {
+            day,
+            phase,
+            stats: StatsNs {
+                samples: 10,
+                min_ns: mean_ns,
+                median_ns: mean_ns,
+                mean_ns,
+                p95_ns: mean_ns,
+                max_ns: mean_ns,
+                stddev_ns,
+            },
+        }
+    }
+
+    #[test]
+    fn comparison() {
+        let old = Baseline::new(
+            "test",
+            10,
+            vec![
+                timing(1, Phase::Parse, 1000.0, 10.0),
+                timing(1, Phase::Part1, 1000.0, 10.0),
+                timing(1, Phase::Part2, 1000.0, 500.0),
+            ],
+        );
+        let new = [
+            timing(1, Phase::Parse, 1000.0, 10.0),
+            timing(1, Phase::Part1, 1200.0, 10.0),
+            timing(1, Phase::Part2, 1200.0, 500.0),
+            timing(2, Phase::Part1, 1.0, 0.0),
+        ];
+
+        let cmp = compare(&old, &new);
+        assert_eq!(cmp.len(), 3);
+        assert!(!cmp[0].significant());
+        assert!(cmp[1].is_regression(0.05));
+        assert!(!cmp[1].is_regression(0.25));
+        assert_eq!(cmp[1].marker(), "***");
+        // a 20% slowdown drowned out by the noise.
+        assert!(!cmp[2].is_regression(0.05));
+    }
+
+    #[test]
+    fn degenerate_comparisons() {
+        // `-n 1` saves a single sample per phase, which has a stddev of 0.
+        let single = |mean_ns| {
+            let mut t = timing(1, Phase::Part1, mean_ns, 0.0);
+            t.stats.samples = 1;
+            t
+        };
+        let old = Baseline::new(
+            "test",
+            1,
+            vec![single(1000.0), timing(2, Phase::Part1, 1000.0, 0.0)],
+        );
+        let new = [single(1116.0), timing(2, Phase::Part1, 1500.0, 0.0)];
+
+        let cmp = compare(&old, &new);
+        assert_eq!(cmp.len(), 2);
+        for c in &cmp {
+            assert_eq!(c.t, 0.0);
+            assert_eq!(c.marker(), "");
+            assert!(!c.is_regression(0.05));
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let baseline = Baseline::new("test", 3, vec![timing(5, Phase::Part2, 12.5, 0.5)]);
+        let json = serde_json::to_string(&baseline).unwrap();
+        let back: Baseline = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.timings, baseline.timings);
+        assert_eq!(back.machine, baseline.machine);
+        assert!(json.contains(r#""phase":"part2""#));
+        // the stats are stored next to the day and phase, not nested in them.
+        assert!(json.contains(r#""mean_ns":12.5"#));
+    }
+}
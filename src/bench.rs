@@ -0,0 +1,250 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    // iterations that are run (and thrown away) before any samples are taken.
+    pub warmup: u32,
+    // how long the measurement phase should roughly take. samples are taken until
+    // this much time has passed (within `min_samples..=max_samples`), unless an
+    // exact number of `samples` is requested.
+    pub target_time: Duration,
+    pub samples: Option<u32>,
+    pub min_samples: u32,
+    pub max_samples: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            target_time: Duration::from_secs(1),
+            samples: None,
+            min_samples: 10,
+            max_samples: 100_000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    // outliers according to tukey's fences, i.e. samples further than 1.5 (mild)
+    // or 3 (severe) interquartile ranges outside of the first and third quartile.
+    pub mild_outliers: usize,
+    pub severe_outliers: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(
+            !samples.is_empty(),
+            "can't compute statistics of zero samples"
+        );
+
+        let mut sorted: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+        let n = sorted.len();
+
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+        let iqr = q3 - q1;
+        let outside = |k: f64| {
+            sorted
+                .iter()
+                .filter(|&&s| s < q1 - k * iqr || s > q3 + k * iqr)
+                .count()
+        };
+        let severe_outliers = outside(3.0);
+
+        Stats {
+            samples: n,
+            min: Duration::from_secs_f64(sorted[0]),
+            max: Duration::from_secs_f64(sorted[n - 1]),
+            median: Duration::from_secs_f64(quantile(&sorted, 0.5)),
+            mean: Duration::from_secs_f64(mean),
+            p95: Duration::from_secs_f64(quantile(&sorted, 0.95)),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            mild_outliers: outside(1.5) - severe_outliers,
+            severe_outliers,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "samples:  {}", self.samples)?;
+        writeln!(f, "min:      {:.3?}", self.min)?;
+        writeln!(f, "median:   {:.3?}", self.median)?;
+        writeln!(f, "mean:     {:.3?} ± {:.3?}", self.mean, self.stddev)?;
+        writeln!(f, "p95:      {:.3?}", self.p95)?;
+        writeln!(f, "max:      {:.3?}", self.max)?;
+        write!(
+            f,
+            "outliers: {} mild, {} severe",
+            self.mild_outliers, self.severe_outliers
+        )
+    }
+}
+
+// `Stats` as they're saved and reported, with all times in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatsNs {
+    pub samples: usize,
+    pub min_ns: f64,
+    pub median_ns: f64,
+    pub mean_ns: f64,
+    pub p95_ns: f64,
+    pub max_ns: f64,
+    pub stddev_ns: f64,
+}
+
+impl From<&Stats> for StatsNs {
+    fn from(stats: &Stats) -> StatsNs {
+        let ns = |d: Duration| d.as_nanos() as f64;
+        StatsNs {
+            samples: stats.samples,
+            min_ns: ns(stats.min),
+            median_ns: ns(stats.median),
+            mean_ns: ns(stats.mean),
+            p95_ns: ns(stats.p95),
+            max_ns: ns(stats.max),
+            stddev_ns: ns(stats.stddev),
+        }
+    }
+}
+
+// linearly interpolated quantile of an already sorted, non-empty slice.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+// phases that are faster than this are timed in batches, so that reading the
+// clock doesn't make up most of every sample.
+const MIN_BATCH_TIME: Duration = Duration::from_micros(10);
+const MAX_BATCH: u32 = 10_000;
+
+// runs `f` `n` times and returns how long that took. the results are only dropped
+// by the next call (or the caller), so freeing them isn't part of the measurement.
+fn time_batch<T>(f: &mut impl FnMut() -> T, results: &mut Vec<T>, n: u32) -> Duration {
+    results.clear();
+    results.reserve(n as usize);
+    let now = Instant::now();
+    for _ in 0..n {
+        results.push(black_box(f()));
+    }
+    now.elapsed()
+}
+
+// benchmarks `f`. every sample is the mean time of a batch of iterations, which
+// only has more than one iteration for phases faster than `MIN_BATCH_TIME`. the
+// warm-up iterations aren't part of the returned statistics, but at least one
+// is always run to pick the batch size.
+pub fn run<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+    let mut results = vec![];
+    let mut fastest = Duration::MAX;
+    for _ in 0..config.warmup.max(1) {
+        fastest = fastest.min(time_batch(&mut f, &mut results, 1));
+    }
+    let batch = if fastest >= MIN_BATCH_TIME {
+        1
+    } else {
+        let per_batch = MIN_BATCH_TIME.as_nanos() / fastest.as_nanos().max(1);
+        (per_batch as u32 + 1).min(MAX_BATCH)
+    };
+
+    let (min, max) = match config.samples {
+        Some(n) => (n.max(1), n.max(1)),
+        None => (
+            config.min_samples.max(1),
+            config.max_samples.max(config.min_samples),
+        ),
+    };
+
+    let start = Instant::now();
+    let mut samples = Vec::with_capacity(min as usize);
+    while samples.len() < min as usize
+        || (samples.len() < max as usize && start.elapsed() < config.target_time)
+    {
+        samples.push(time_batch(&mut f, &mut results, batch) / batch);
+    }
+
+    Stats::from_samples(&samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics() {
+        let samples: Vec<_> = [5, 1, 4, 2, 3, 100].map(Duration::from_millis).into();
+        let stats = Stats::from_samples(&samples);
+
+        let ms = |d: Duration| (d.as_secs_f64() * 1e6).round() / 1e3;
+        assert_eq!(stats.samples, 6);
+        assert_eq!(ms(stats.min), 1.0);
+        assert_eq!(ms(stats.max), 100.0);
+        assert_eq!(ms(stats.median), 3.5);
+        assert_eq!(ms(stats.mean), 19.167);
+        assert_eq!(ms(stats.p95), 76.25);
+        assert_eq!((stats.mild_outliers, stats.severe_outliers), (0, 1));
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&[Duration::from_millis(7)]);
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn fixed_sample_count() {
+        let config = Config {
+            warmup: 0,
+            samples: Some(17),
+            ..Config::default()
+        };
+        assert_eq!(run(&config, || ()).samples, 17);
+
+        let config = Config {
+            target_time: Duration::ZERO,
+            min_samples: 4,
+            ..Config::default()
+        };
+        assert_eq!(run(&config, || ()).samples, 4);
+    }
+
+    #[test]
+    fn fast_phases_are_batched() {
+        let config = Config {
+            warmup: 1,
+            samples: Some(3),
+            ..Config::default()
+        };
+        let mut calls = 0;
+        let stats = run(&config, || calls += 1);
+        assert_eq!(stats.samples, 3);
+        assert!(calls > 1 + 3, "only {calls} calls");
+    }
+}
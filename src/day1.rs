@@ -1,34 +1,77 @@
 use ahash::AHashMap;
 
-pub fn part1(input: &str) -> String {
+use crate::parse::Source;
+
+pub const NAME: &str = "Historian Hysteria";
+pub const IMPLEMENTED: bool = true;
+
+// both lists are sorted, which part 1 needs and part 2 doesn't care about.
+pub struct Input {
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
     let (mut left, mut right) = (vec![], vec![]);
-    for line in input.lines() {
+    for line in src.lines() {
         let mut s = line.split_ascii_whitespace();
-        left.push(s.next().unwrap().parse::<isize>().unwrap());
-        right.push(s.next().unwrap().parse::<isize>().unwrap());
+        left.push(src.next_num(&mut s, line)?);
+        right.push(src.next_num(&mut s, line)?);
     }
 
     left.sort_unstable();
     right.sort_unstable();
-    left.iter()
-        .zip(right.iter())
-        .map(|(&i, &j)| (i - j).unsigned_abs())
+    Ok(Input { left, right })
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .left
+        .iter()
+        .zip(input.right.iter())
+        .map(|(&i, &j)| i.abs_diff(j))
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let (mut left, mut right) = (vec![], AHashMap::new());
-    for line in input.lines() {
-        let mut s = line.split_ascii_whitespace();
-        left.push(s.next().unwrap().parse::<usize>().unwrap());
-        *right
-            .entry(s.next().unwrap().parse::<usize>().unwrap())
-            .or_default() += 1;
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let mut right = AHashMap::new();
+    for &j in &input.right {
+        *right.entry(j).or_default() += 1;
     }
 
-    left.into_iter()
-        .map(|i| i * right.get(&i).map_or(0, |&j| j))
+    Ok(input
+        .left
+        .iter()
+        .map(|&i| i * right.get(&i).map_or(0, |&j| j))
         .sum::<usize>()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "11");
+        assert_eq!(part2(&input).unwrap(), "31");
+    }
+
+    #[test]
+    fn missing_number() {
+        let err = parse("3   4\n4\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: expected a number, found end of line");
+    }
 }
@@ -1,51 +1,29 @@
 use ahash::AHashMap;
 use memchr::memchr_iter;
 
-struct Grid {
-    data: Vec<u8>,
-    width: usize,
-    height: usize,
-}
-
-impl Grid {
-    fn parse(input: &str) -> Grid {
-        let (mut width, mut height) = (0, 0);
-        let mut data = vec![];
-        for line in input.lines() {
-            width = line.len();
-            data.extend(line.bytes().map(|b| b.wrapping_sub(b'0')));
-            height += 1;
-        }
-
-        Grid { data, width, height }
-    }
+use crate::grid::{Grid, Pos};
 
-    fn get(&self, x: usize, y: usize) -> u8 {
-        assert!(x < self.width && y < self.height);
+pub const NAME: &str = "Hoof It";
+pub const IMPLEMENTED: bool = true;
 
-        self.data[y * self.width + x]
-    }
-}
-
-fn tile_score(g: &Grid, x: usize, y: usize, part2: bool) -> usize {
-    fn dfs(g: &Grid, x: usize, y: usize, level: u8, peaks: &mut AHashMap<(usize, usize), usize>) {
+fn tile_score(g: &Grid<u8>, pos: Pos, part2: bool) -> usize {
+    fn dfs(g: &Grid<u8>, pos: Pos, level: u8, peaks: &mut AHashMap<Pos, usize>) {
         if level == 9 {
-            *peaks.entry((x, y)).or_default() += 1;
+            *peaks.entry(pos).or_default() += 1;
             return;
         }
-        for (dx, dy) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
-            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-            if nx < g.width && ny < g.height && g.get(nx, ny) == level + 1 {
-                dfs(g, nx, ny, level + 1, peaks);
+        for next in g.neighbours(pos) {
+            if g.at(next) == level + 1 {
+                dfs(g, next, level + 1, peaks);
             }
         }
     }
 
-    if g.get(x, y) != 0 {
+    if g.at(pos) != 0 {
         0
     } else {
         let mut peaks = AHashMap::new();
-        dfs(g, x, y, 0, &mut peaks);
+        dfs(g, pos, 0, &mut peaks);
         if !part2 {
             peaks.len()
         } else {
@@ -54,20 +32,65 @@ fn tile_score(g: &Grid, x: usize, y: usize, part2: bool) -> usize {
     }
 }
 
-pub fn part1(input: &str) -> String {
-    let grid = Grid::parse(input);
+pub type Input = Grid<u8>;
 
-    memchr_iter(0, &grid.data)
-        .map(|i| tile_score(&grid, i % grid.width, i / grid.width, false))
-        .sum::<usize>()
-        .to_string()
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    // the examples use '.' for impassable tiles, which just turns into
+    // a height that never matches any neighbour.
+    Grid::parse(input, "a height digit or '.'", |_, b| {
+        (b.is_ascii_digit() || b == b'.').then(|| b.wrapping_sub(b'0'))
+    })
 }
 
-pub fn part2(input: &str) -> String {
-    let grid = Grid::parse(input);
+pub fn part1(grid: &Input) -> anyhow::Result<String> {
+    Ok(memchr_iter(0, grid.as_slice())
+        .map(|i| tile_score(grid, grid.pos(i), false))
+        .sum::<usize>()
+        .to_string())
+}
 
-    memchr_iter(0, &grid.data)
-        .map(|i| tile_score(&grid, i % grid.width, i / grid.width, true))
+pub fn part2(grid: &Input) -> anyhow::Result<String> {
+    Ok(memchr_iter(0, grid.as_slice())
+        .map(|i| tile_score(grid, grid.pos(i), true))
         .sum::<usize>()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "36");
+        assert_eq!(part2(&input).unwrap(), "81");
+    }
+
+    #[test]
+    fn impassable_tiles() {
+        let input = "\
+...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9
+";
+        let input = parse(input).unwrap();
+        assert_eq!(part1(&input).unwrap(), "2");
+        assert_eq!(part2(&input).unwrap(), "2");
+    }
 }
@@ -1,12 +1,23 @@
 use ahash::AHashMap;
 
-fn parse_input(input: &str) -> Vec<usize> {
-    input.trim().split_ascii_whitespace().map(|s| s.parse().unwrap()).collect()
+use crate::parse::Source;
+
+pub const NAME: &str = "Plutonian Pebbles";
+pub const IMPLEMENTED: bool = true;
+
+pub struct Input {
+    stones: Vec<usize>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let stones = input.split_ascii_whitespace().map(|s| src.num(s)).collect::<anyhow::Result<_>>()?;
+    Ok(Input { stones })
 }
 
 fn split(n: usize) -> Option<(usize, usize)> {
-    let digits = n.ilog10() + 1;
-    if digits % 2 == 0 {
+    let digits = n.checked_ilog10().unwrap_or(0) + 1;
+    if digits.is_multiple_of(2) {
         let div = 10usize.pow(digits / 2);
         Some((n / div, n % div))
     } else {
@@ -23,7 +34,10 @@ fn count(stones: &[usize]) -> AHashMap<usize, usize> {
     res
 }
 
-fn blink_count(stones: &AHashMap<usize, usize>, out: &mut AHashMap<usize, usize>) {
+fn blink_count(
+    stones: &AHashMap<usize, usize>,
+    out: &mut AHashMap<usize, usize>,
+) -> anyhow::Result<()> {
     for (&s, &count) in stones {
         if s == 0 {
             *out.entry(1).or_default() += count;
@@ -31,31 +45,64 @@ fn blink_count(stones: &AHashMap<usize, usize>, out: &mut AHashMap<usize, usize>
             *out.entry(l).or_default() += count;
             *out.entry(r).or_default() += count;
         } else {
-            *out.entry(s.checked_mul(2024).unwrap()).or_default() += count;
+            let Some(next) = s.checked_mul(2024) else {
+                anyhow::bail!("Stone {s} overflows after multiplying by 2024");
+            };
+            *out.entry(next).or_default() += count;
         }
     }
+    Ok(())
 }
 
-pub fn part1(input: &str) -> String {
-    let mut stones = count(&parse_input(input));
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let mut stones = count(&input.stones);
     let mut prev = AHashMap::default();
     for _ in 0..25 {
         std::mem::swap(&mut stones, &mut prev);
         stones.clear();
-        blink_count(&prev, &mut stones);
+        blink_count(&prev, &mut stones)?;
     }
 
-    stones.values().sum::<usize>().to_string()
+    Ok(stones.values().sum::<usize>().to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let mut stones = count(&parse_input(input));
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let mut stones = count(&input.stones);
     let mut prev = AHashMap::default();
     for _ in 0..75 {
         std::mem::swap(&mut stones, &mut prev);
         stones.clear();
-        blink_count(&prev, &mut stones);
+        blink_count(&prev, &mut stones)?;
+    }
+
+    Ok(stones.values().sum::<usize>().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse("125 17\n").unwrap();
+        assert_eq!(part1(&input).unwrap(), "55312");
+        assert_eq!(part2(&input).unwrap(), "65601038650482");
     }
 
-    stones.values().sum::<usize>().to_string()
+    #[test]
+    fn splitting() {
+        assert_eq!(split(0), None);
+        assert_eq!(split(7), None);
+        assert_eq!(split(17), Some((1, 7)));
+        assert_eq!(split(1000), Some((10, 0)));
+        assert_eq!(split(253000), Some((253, 0)));
+        assert_eq!(split(12345), None);
+    }
+
+    #[test]
+    fn overflowing_stone() {
+        let input = parse("1000000000000000000\n").unwrap();
+        let expected = "Stone 1000000000000000000 overflows after multiplying by 2024";
+        assert_eq!(part1(&input).err().unwrap().to_string(), expected);
+    }
 }
@@ -1,8 +1,123 @@
+use crate::grid::{BitGrid, Dir, Dir8, Grid};
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Garden Groups";
+pub const IMPLEMENTED: bool = true;
+
+struct Region {
+    area: usize,
+    perimeter: usize,
+    // a polygon has as many sides as it has corners, which are much easier to
+    // count locally than the sides themselves.
+    sides: usize,
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+fn regions(g: &Grid<u8>) -> Vec<Region> {
+    let mut seen = BitGrid::new(g.width(), g.height(), false);
+    let mut stack = vec![];
+    let mut regions = vec![];
+
+    for start in g.positions() {
+        if seen.at(start) {
+            continue;
+        }
+        let plant = g.at(start);
+        let mut region = Region { area: 0, perimeter: 0, sides: 0 };
+        seen.set(start, true);
+        stack.push(start);
+
+        while let Some(pos) = stack.pop() {
+            let same = |d: Dir8| g.step(pos, d).map(|p| g.at(p)) == Some(plant);
+            region.area += 1;
+
+            for d in Dir::ALL {
+                match g.step(pos, d) {
+                    Some(next) if g.at(next) == plant => {
+                        if !seen.at(next) {
+                            seen.set(next, true);
+                            stack.push(next);
+                        }
+                    }
+                    _ => region.perimeter += 1,
+                }
+            }
+
+            // every tile is checked for a corner in each of its four diagonal
+            // directions. it's a convex corner if neither neighbour towards it is
+            // part of the region, and a concave one if both are, but the diagonal
+            // isn't. the latter also covers regions nested inside this one.
+            for d in Dir8::DIAGONALS {
+                let (a, b) = (same(d.turn_left()), same(d.turn_right()));
+                if (!a && !b) || (a && b && !same(d)) {
+                    region.sides += 1;
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    regions
+}
+
+pub type Input = Grid<u8>;
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    Grid::parse(input, "a plant letter", |_, b| b.is_ascii_uppercase().then_some(b))
+}
+
+pub fn part1(grid: &Input) -> anyhow::Result<String> {
+    Ok(regions(grid).iter().map(|r| r.area * r.perimeter).sum::<usize>().to_string())
+}
+
+pub fn part2(grid: &Input) -> anyhow::Result<String> {
+    Ok(regions(grid).iter().map(|r| r.area * r.sides).sum::<usize>().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "1930");
+        assert_eq!(part2(&input).unwrap(), "1206");
+    }
+
+    #[test]
+    fn nested_regions() {
+        let input = "\
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO
+";
+        let input = parse(input).unwrap();
+        assert_eq!(part1(&input).unwrap(), "772");
+        assert_eq!(part2(&input).unwrap(), "436");
+
+        let input = "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), "368");
+    }
+}
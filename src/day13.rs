@@ -1,8 +1,174 @@
+use crate::parse::Source;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Claw Contraption";
+pub const IMPLEMENTED: bool = true;
+
+struct Machine {
+    a: (i128, i128),
+    b: (i128, i128),
+    prize: (i128, i128),
+}
+
+pub struct Input {
+    machines: Vec<Machine>,
+}
+
+// parses e.g. `Button A: X+94, Y+34`, where `label` is `Button A: ` and `sign` is `+`.
+fn parse_pair<'a>(
+    src: &Source<'a>,
+    line: &'a str,
+    label: &str,
+    sign: char,
+) -> anyhow::Result<(i128, i128)> {
+    let Some(rest) = line.strip_prefix(label) else {
+        return Err(src.error(line, format_args!("{label:?}")));
+    };
+    let (x, y) = src.split_once(rest, ", ")?;
+    let coord = |s: &'a str, axis: char| {
+        let prefix = format!("{axis}{sign}");
+        match s.strip_prefix(&prefix) {
+            Some(n) => src.num::<u64>(n).map(i128::from),
+            None => Err(src.error(s, format_args!("{prefix:?}"))),
+        }
+    };
+    Ok((coord(x, 'X')?, coord(y, 'Y')?))
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut lines = src.lines().filter(|l| !l.is_empty());
+    let mut machines = vec![];
+
+    while let Some(first) = lines.next() {
+        let a = parse_pair(&src, first, "Button A: ", '+')?;
+        let b = parse_pair(&src, src.next(&mut lines, first, "Button B")?, "Button B: ", '+')?;
+        let prize = src.next(&mut lines, first, "Prize")?;
+        let prize = parse_pair(&src, prize, "Prize: ", '=')?;
+        machines.push(Machine { a, b, prize });
+    }
+
+    Ok(Input { machines })
+}
+
+fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, s, t) = ext_gcd(b, a % b);
+        (g, t, s - a / b * t)
+    }
+}
+
+// the cheapest way to write `t` as `a * u + b * v` with nonnegative `a` and `b`.
+// this is what's left of the problem if both buttons move the claw along the
+// same line, so there can be more than one solution.
+fn cheapest_1d(u: i128, v: i128, t: i128) -> Option<i128> {
+    let cost = |a: i128, b: i128| (a >= 0 && b >= 0).then_some(3 * a + b);
+    match (u, v) {
+        (0, 0) => (t == 0).then_some(0),
+        (0, v) => (t % v == 0).then(|| cost(0, t / v)).flatten(),
+        (u, 0) => (t % u == 0).then(|| cost(t / u, 0)).flatten(),
+        (u, v) => {
+            let (g, s, r) = ext_gcd(u, v);
+            if t % g != 0 {
+                return None;
+            }
+            // all solutions are `(a + k * v/g, b - k * u/g)`, so the cost is linear
+            // in `k` and one of the two extremes has to be the cheapest. those are
+            // the fewest possible presses of either button.
+            let a = (s * (t / g)).rem_euclid(v / g);
+            let b = (r * (t / g)).rem_euclid(u / g);
+            [cost(a, (t - a * u) / v), cost((t - b * v) / u, b)].into_iter().flatten().min()
+        }
+    }
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+// the fewest tokens needed to win the prize, if that's possible at all.
+fn cheapest(m: &Machine, offset: i128) -> Option<i128> {
+    let ((ax, ay), (bx, by)) = (m.a, m.b);
+    let (px, py) = (m.prize.0 + offset, m.prize.1 + offset);
+
+    // cramer's rule. the solution is unique, so it only has to be integral.
+    let det = ax * by - ay * bx;
+    if det != 0 {
+        let (a, b) = (px * by - py * bx, ax * py - ay * px);
+        if a % det != 0 || b % det != 0 {
+            return None;
+        }
+        let (a, b) = (a / det, b / det);
+        return (a >= 0 && b >= 0).then_some(3 * a + b);
+    }
+
+    // both buttons are collinear, so the prize has to be on their line as well.
+    if ax * py - ay * px != 0 || bx * py - by * px != 0 {
+        return None;
+    }
+    if ax == 0 && bx == 0 {
+        if px != 0 {
+            return None;
+        }
+        return cheapest_1d(ay, by, py);
+    }
+    cheapest_1d(ax, bx, px)
+}
+
+fn total_cost(input: &Input, offset: i128) -> i128 {
+    input.machines.iter().filter_map(|m| cheapest(m, offset)).sum()
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(total_cost(input, 0).to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(total_cost(input, 10_000_000_000_000).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "480");
+        assert_eq!(part2(&input).unwrap(), "875318608908");
+    }
+
+    #[test]
+    fn collinear_buttons() {
+        let machine = |a, b, prize| Machine { a, b, prize };
+        // b is cheaper per distance, a is only needed to fix up the remainder.
+        assert_eq!(cheapest(&machine((3, 3), (2, 2), (7, 7)), 0), Some(5));
+        assert_eq!(cheapest(&machine((2, 2), (1, 1), (10, 10)), 0), Some(10));
+        // here it's the other way around.
+        assert_eq!(cheapest(&machine((6, 6), (1, 1), (13, 13)), 0), Some(7));
+        assert_eq!(cheapest(&machine((4, 4), (6, 6), (7, 7)), 0), None);
+        assert_eq!(cheapest(&machine((1, 1), (2, 2), (3, 4)), 0), None);
+        assert_eq!(cheapest(&machine((0, 2), (0, 1), (0, 5)), 0), Some(5));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("Button A: X+1, Y+2\nButton C: X+3, Y+4\n").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 1: expected \"Button B: \", found \"Button\"");
+    }
+}
@@ -1,8 +1,169 @@
+use crate::parse::Source;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Restroom Redoubt";
+pub const IMPLEMENTED: bool = true;
+
+// the size of the real arena. the example uses a smaller one.
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
+
+struct Robot {
+    pos: (i64, i64),
+    vel: (i64, i64),
+}
+
+pub struct Input {
+    robots: Vec<Robot>,
+    // the arena size, which defaults to the real one's.
+    pub width: i64,
+    pub height: i64,
+}
+
+// parses e.g. `v=-3,2`, where `label` is `v=`.
+fn parse_vec<'a>(src: &Source<'a>, s: &'a str, label: &str) -> anyhow::Result<(i64, i64)> {
+    let Some(rest) = s.strip_prefix(label) else {
+        return Err(src.error(s, format_args!("{label:?}")));
+    };
+    let (x, y) = src.split_once(rest, ",")?;
+    Ok((src.num(x)?, src.num(y)?))
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let robots = src
+        .lines()
+        .map(|l| {
+            let (p, v) = src.split_once(l, " ")?;
+            Ok(Robot { pos: parse_vec(&src, p, "p=")?, vel: parse_vec(&src, v, "v=")? })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input {
+        robots,
+        width: WIDTH,
+        height: HEIGHT,
+    })
+}
+
+// every coordinate moves independently and wraps around, so the positions after
+// any number of seconds can be computed directly.
+fn position(r: &Robot, seconds: i64, width: i64, height: i64) -> (i64, i64) {
+    (
+        (r.pos.0 + r.vel.0 * seconds).rem_euclid(width),
+        (r.pos.1 + r.vel.1 * seconds).rem_euclid(height),
+    )
+}
+
+fn safety_factor(input: &Input, seconds: i64) -> usize {
+    let (width, height) = (input.width, input.height);
+    let mut quadrants = [0; 4];
+    for r in &input.robots {
+        let (x, y) = position(r, seconds, width, height);
+        // robots exactly in the middle don't count towards any quadrant.
+        if x == width / 2 || y == height / 2 {
+            continue;
+        }
+        quadrants[(x > width / 2) as usize + 2 * (y > height / 2) as usize] += 1;
+    }
+    quadrants.iter().product()
+}
+
+// `n` times the variance of the `coord` of all robots after each of `0..period` seconds,
+// returning the time with the smallest one.
+fn least_spread(input: &Input, period: i64, coord: impl Fn(&Robot, i64) -> i64) -> i64 {
+    let n = input.robots.len() as i64;
+    (0..period)
+        .min_by_key(|&t| {
+            let (sum, sum_sq) = input.robots.iter().fold((0, 0), |(s, sq), r| {
+                let c = coord(r, t);
+                (s + c, sq + c * c)
+            });
+            n * sum_sq - sum * sum
+        })
+        .unwrap_or(0)
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+// the picture of the tree is a dense cluster of robots, so that's when both the
+// x and y coordinates are least spread out. the x coordinates repeat every `width`
+// seconds and the y coordinates every `height` seconds, so both can be searched
+// separately and then combined with the chinese remainder theorem.
+fn tree_time(input: &Input) -> anyhow::Result<i64> {
+    let (width, height) = (input.width, input.height);
+    let tx = least_spread(input, width, |r, t| position(r, t, width, height).0);
+    let ty = least_spread(input, height, |r, t| position(r, t, width, height).1);
+
+    // t = tx + width * k with tx + width * k = ty (mod height).
+    let Some(inv) = (1..height).find(|i| (width * i) % height == 1) else {
+        anyhow::bail!("The arena size {width}x{height} has no unique period");
+    };
+    let k = ((ty - tx) * inv).rem_euclid(height);
+    Ok(tx + width * k)
+}
+
+fn check_size(input: &Input) -> anyhow::Result<()> {
+    if input.width <= 0 || input.height <= 0 {
+        anyhow::bail!("The arena size {}x{} is empty", input.width, input.height);
+    }
+    Ok(())
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    check_size(input)?;
+    Ok(safety_factor(input, 100).to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    check_size(input)?;
+    Ok(tree_time(input)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+
+    #[test]
+    fn example() {
+        let mut input = parse(EXAMPLE).unwrap();
+        (input.width, input.height) = (11, 7);
+        assert_eq!(part1(&input).unwrap(), "12");
+
+        let r = Robot { pos: (2, 4), vel: (2, -3) };
+        assert_eq!(position(&r, 5, 11, 7), (1, 3));
+    }
+
+    #[test]
+    fn finds_cluster() {
+        // robots that all end up within a 3x3 square after 58 seconds, but start
+        // out scattered all over the place.
+        let (width, height, time) = (11, 7, 58);
+        let robots = (0..30)
+            .map(|i: i64| {
+                let vel = ((i * 7) % 11 - 5, (i * 3) % 7 - 3);
+                let end = (4 + i % 3, 2 + (i / 3) % 3);
+                let pos = (end.0 - vel.0 * time, end.1 - vel.1 * time);
+                Robot { pos: (pos.0.rem_euclid(width), pos.1.rem_euclid(height)), vel }
+            })
+            .collect();
+        let mut input = Input { robots, width, height };
+        assert_eq!(part2(&input).unwrap(), time.to_string());
+        (input.width, input.height) = (10, 4);
+        assert!(part2(&input).is_err());
+        input.width = 0;
+        assert!(part1(&input).is_err());
+    }
+}
@@ -1,8 +1,217 @@
+use crate::{
+    grid::{Dir, Grid, Pos},
+    parse::Source,
+};
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Warehouse Woes";
+pub const IMPLEMENTED: bool = true;
+
+// part 2's map, where everything except the robot is twice as wide.
+fn widen(g: &Grid<u8>) -> Grid<u8> {
+    let cells = g.as_slice().iter().flat_map(|&b| match b {
+        b'O' => *b"[]",
+        b'@' => *b"@.",
+        b => [b, b],
+    });
+    Grid::from_cells(g.width() * 2, g.height(), cells)
+}
+
+// whether whatever is at `pos` can be moved one step in `dir`, pushing along
+// everything in its way. anything off the map behaves like a wall, so maps
+// without a border work too.
+fn can_move(g: &Grid<u8>, pos: Pos, dir: Dir) -> bool {
+    let Some(next @ (nx, ny)) = g.step(pos, dir) else {
+        return false;
+    };
+    match g.at(next) {
+        b'.' => true,
+        // pushing a wide box up or down also pushes whatever is in front of
+        // its other half.
+        b'[' if dir.is_vertical() => can_move(g, next, dir) && can_move(g, (nx + 1, ny), dir),
+        b']' if dir.is_vertical() => can_move(g, next, dir) && can_move(g, (nx - 1, ny), dir),
+        b'O' | b'[' | b']' => can_move(g, next, dir),
+        _ => false,
+    }
+}
+
+// moves whatever is at `pos` one step in `dir`. this has to be checked with
+// `can_move` first, since nothing is moved back if a push fails halfway.
+fn do_move(g: &mut Grid<u8>, pos: Pos, dir: Dir) {
+    let next @ (nx, ny) = g.step(pos, dir).expect("moves are checked with can_move first");
+    match g.at(next) {
+        b'[' if dir.is_vertical() => {
+            do_move(g, next, dir);
+            do_move(g, (nx + 1, ny), dir);
+        }
+        b']' if dir.is_vertical() => {
+            do_move(g, next, dir);
+            do_move(g, (nx - 1, ny), dir);
+        }
+        b'O' | b'[' | b']' => do_move(g, next, dir),
+        // if two boxes push the same box, the second one finds it already gone.
+        _ => {}
+    }
+    g.set(next, g.at(pos));
+    g.set(pos, b'.');
+}
+
+fn gps_sum(g: &Grid<u8>) -> usize {
+    g.iter().filter(|&(_, b)| b == b'O' || b == b'[').map(|((x, y), _)| 100 * y + x).sum()
+}
+
+pub struct Input {
+    grid: Grid<u8>,
+    robot: Pos,
+    moves: Vec<Dir>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    // the map ends at the first empty line, and the moves follow after it.
+    let blank = src.lines().find(|l| l.is_empty()).unwrap_or(src.end_of(input));
+    let map = &input[..blank.as_ptr() as usize - input.as_ptr() as usize];
+
+    let mut robot = None;
+    let grid = Grid::parse(map, "'.', '#', 'O' or a single '@'", |pos, b| match b {
+        b'@' if robot.is_none() => {
+            robot = Some(pos);
+            Some(b)
+        }
+        b'.' | b'#' | b'O' => Some(b),
+        _ => None,
+    })?;
+    let robot = robot.ok_or_else(|| src.error(src.end_of(input), "a '@' somewhere in the map"))?;
+
+    let mut moves = vec![];
+    for line in src.lines().skip_while(|l| !l.is_empty()) {
+        for (col, b) in line.bytes().enumerate() {
+            moves.push(match b {
+                b'^' => Dir::Up,
+                b'v' => Dir::Down,
+                b'<' => Dir::Left,
+                b'>' => Dir::Right,
+                _ => return Err(src.error(&line[col..], "one of '^', 'v', '<' or '>'")),
+            });
+        }
+    }
+
+    Ok(Input { grid, robot, moves })
+}
+
+fn simulate(mut grid: Grid<u8>, mut robot: Pos, moves: &[Dir]) -> usize {
+    for &dir in moves {
+        if can_move(&grid, robot, dir) {
+            do_move(&mut grid, robot, dir);
+            robot = grid.step(robot, dir).unwrap();
+        }
+    }
+    gps_sum(&grid)
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(simulate(input.grid.clone(), input.robot, &input.moves).to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let robot = (input.robot.0 * 2, input.robot.1);
+    Ok(simulate(widen(&input.grid), robot, &input.moves).to_string())
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+    const SMALL_EXAMPLE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "10092");
+        assert_eq!(part2(&input).unwrap(), "9021");
+        assert_eq!(part1(&parse(SMALL_EXAMPLE).unwrap()).unwrap(), "2028");
+    }
+
+    #[test]
+    fn small_wide_example() {
+        let input = "\
+#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^
+";
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), "618");
+    }
+
+    #[test]
+    fn cascading_push() {
+        let grid = |rows: &[&str]| {
+            Grid::from_cells(rows[0].len(), rows.len(), rows.concat().into_bytes())
+        };
+        // the box in front of the robot rests on two others, which both rest on
+        // the same box again. that one is stuck, so nothing may move.
+        let mut warehouse = grid(&[
+            "##########",
+            "##..#...##",
+            "##..[]..##",
+            "##.[][].##",
+            "##..[]..##",
+            "##..@...##",
+            "##########",
+        ]);
+        assert!(!can_move(&warehouse, (4, 5), Dir::Up));
+
+        warehouse.set((4, 1), b'.');
+        assert!(can_move(&warehouse, (4, 5), Dir::Up));
+        do_move(&mut warehouse, (4, 5), Dir::Up);
+        let moved = grid(&[
+            "##########",
+            "##..[]..##",
+            "##.[][].##",
+            "##..[]..##",
+            "##..@...##",
+            "##......##",
+            "##########",
+        ]);
+        assert_eq!(warehouse, moved);
+    }
+}
@@ -1,8 +1,168 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::grid::{Dir, Grid, Pos};
+
+pub const NAME: &str = "Reindeer Maze";
+pub const IMPLEMENTED: bool = true;
+
+const STEP_COST: u32 = 1;
+const TURN_COST: u32 = 1000;
+
+pub struct Input {
+    walls: Grid<bool>,
+    start: Pos,
+    end: Pos,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let expected = "'.', '#' or a single 'S' and 'E'";
+    let markers = [(b'S', false), (b'E', false)];
+    let (walls, [start, end]) = Grid::parse_with_markers(input, expected, markers, |_, b| {
+        (b == b'.' || b == b'#').then_some(b == b'#')
+    })?;
+    Ok(Input { walls, start, end })
+}
+
+impl Input {
+    // states are a tile and the direction the reindeer is facing on it. the
+    // directions are in clockwise order, so turning clockwise is adding 1.
+    fn state(&self, pos: Pos, dir: Dir) -> usize {
+        self.walls.cell_index(pos) * 4 + dir.index()
+    }
+
+    fn step(&self, state: usize, forward: bool) -> Option<usize> {
+        let (pos, dir) = (self.walls.pos(state / 4), Dir::ALL[state % 4]);
+        let next = self.walls.step(pos, if forward { dir } else { dir.reverse() })?;
+        (!self.walls.at(next)).then(|| self.state(next, dir))
+    }
+
+    // all states reachable from `state` in a single move, and what that move costs.
+    // moves are reversible, so these are also all the states that reach `state`
+    // if `forward` is unset.
+    fn moves(&self, state: usize, forward: bool) -> impl Iterator<Item = (usize, u32)> {
+        let turns = [1, 3].map(|t| (state - state % 4 + (state + t) % 4, TURN_COST));
+        self.step(state, forward).map(|s| (s, STEP_COST)).into_iter().chain(turns)
+    }
+
+    // dijkstra from the start, facing east. returns the distance to every state,
+    // with unreachable ones left at `u32::MAX`.
+    fn distances(&self) -> Vec<u32> {
+        let mut dist = vec![u32::MAX; self.walls.width() * self.walls.height() * 4];
+        let mut heap = BinaryHeap::new();
+        let start = self.state(self.start, Dir::Right);
+        dist[start] = 0;
+        heap.push(Reverse((0, start)));
+
+        while let Some(Reverse((d, state))) = heap.pop() {
+            if d > dist[state] {
+                continue;
+            }
+            for (next, cost) in self.moves(state, true) {
+                if d + cost < dist[next] {
+                    dist[next] = d + cost;
+                    heap.push(Reverse((d + cost, next)));
+                }
+            }
+        }
+        dist
+    }
+
+    fn end_states(&self) -> [usize; 4] {
+        Dir::ALL.map(|dir| self.state(self.end, dir))
+    }
+}
+
+fn lowest_score(input: &Input, dist: &[u32]) -> anyhow::Result<u32> {
+    match input.end_states().map(|s| dist[s]).into_iter().min() {
+        Some(d) if d != u32::MAX => Ok(d),
+        _ => anyhow::bail!("The end of the maze is unreachable"),
+    }
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(lowest_score(input, &input.distances())?.to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let dist = input.distances();
+    let best = lowest_score(input, &dist)?;
+
+    // walk backwards from every optimal end state, only following moves that are
+    // on some shortest path, i.e. whose cost makes up the entire difference.
+    let mut on_path = vec![false; dist.len()];
+    let mut stack: Vec<_> = input.end_states().into_iter().filter(|&s| dist[s] == best).collect();
+    for &s in &stack {
+        on_path[s] = true;
+    }
+    while let Some(state) = stack.pop() {
+        for (prev, cost) in input.moves(state, false) {
+            if !on_path[prev] && dist[prev].checked_add(cost) == Some(dist[state]) {
+                on_path[prev] = true;
+                stack.push(prev);
+            }
+        }
+    }
+
+    let tiles = on_path.chunks(4).filter(|dirs| dirs.contains(&true)).count();
+    Ok(tiles.to_string())
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    const SECOND_EXAMPLE: &str = "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "7036");
+        assert_eq!(part2(&input).unwrap(), "45");
+
+        let input = parse(SECOND_EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "11048");
+        assert_eq!(part2(&input).unwrap(), "64");
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let input = parse("#####\n#S#E#\n#####\n").unwrap();
+        assert!(part1(&input).is_err());
+    }
+}
@@ -1,8 +1,176 @@
+use itertools::Itertools;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::parse::Source;
+
+pub const NAME: &str = "Chronospatial Computer";
+pub const IMPLEMENTED: bool = true;
+
+// programs that loop forever are a thing, so give up at some point instead of hanging.
+const MAX_STEPS: usize = 10_000_000;
+
+pub struct Input {
+    a: u64,
+    b: u64,
+    c: u64,
+    program: Vec<u8>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut lines = src.lines().filter(|l| !l.is_empty());
+    let mut register = |name: &str| {
+        let label = format!("Register {name}: ");
+        let line = src.next(&mut lines, src.end_of(input), format_args!("{label:?}"))?;
+        match line.strip_prefix(&label) {
+            Some(n) => src.num(n),
+            None => Err(src.error(line, format_args!("{label:?}"))),
+        }
+    };
+    let (a, b, c) = (register("A")?, register("B")?, register("C")?);
+
+    let line = src.next(&mut lines, src.end_of(input), "\"Program: \"")?;
+    let Some(program) = line.strip_prefix("Program: ") else {
+        return Err(src.error(line, "\"Program: \""));
+    };
+    let program = program
+        .split(',')
+        .map(|n| match src.num::<u8>(n) {
+            Ok(n) if n < 8 => Ok(n),
+            _ => Err(src.error(n, "a 3-bit number")),
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input { a, b, c, program })
+}
+
+fn run(program: &[u8], mut a: u64, mut b: u64, mut c: u64) -> anyhow::Result<Vec<u8>> {
+    let mut out = vec![];
+    let mut ip = 0;
+
+    for _ in 0..MAX_STEPS {
+        // jumping past the end (or into the middle of the last instruction) halts.
+        let (Some(&op), Some(&operand)) = (program.get(ip), program.get(ip + 1)) else {
+            return Ok(out);
+        };
+        let combo = || match operand {
+            0..=3 => Ok(operand as u64),
+            4 => Ok(a),
+            5 => Ok(b),
+            6 => Ok(c),
+            _ => anyhow::bail!("Combo operand 7 is reserved (at instruction {ip})"),
+        };
+        // the shifts can be as large as any register, which just shifts everything out.
+        let div = |n: u64, shift: u64| {
+            n.checked_shr(shift.try_into().unwrap_or(u32::MAX)).unwrap_or(0)
+        };
+
+        match op {
+            0 => a = div(a, combo()?),
+            1 => b ^= operand as u64,
+            2 => b = combo()? & 7,
+            3 if a != 0 => {
+                ip = operand as usize;
+                continue;
+            }
+            3 => {}
+            4 => b ^= c,
+            5 => out.push((combo()? & 7) as u8),
+            6 => b = div(a, combo()?),
+            _ => c = div(a, combo()?),
+        }
+        ip += 2;
+    }
+
+    anyhow::bail!("The program didn't halt after {MAX_STEPS} steps")
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(run(&input.program, input.a, input.b, input.c)?.iter().join(","))
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+// the smallest value of `a` that makes the program output itself. this relies on
+// the program being a single loop that outputs one value per iteration, shifts `a`
+// right by 3 bits and stops once it's 0, with the output only depending on `a`.
+// the last output then only depends on the highest octal digit of `a`, the one
+// before on the two highest digits and so on, so `a` can be built up one digit at
+// a time, going backwards through the program.
+fn find_quine(input: &Input) -> anyhow::Result<Option<u64>> {
+    fn search(input: &Input, a: u64, i: usize) -> anyhow::Result<Option<u64>> {
+        for digit in 0..8 {
+            let a = a << 3 | digit;
+            if run(&input.program, a, input.b, input.c)? != input.program[i..] {
+                continue;
+            }
+            if i == 0 {
+                return Ok(Some(a));
+            }
+            if let Some(a) = search(input, a, i - 1)? {
+                return Ok(Some(a));
+            }
+        }
+        Ok(None)
+    }
+
+    let instructions: Vec<_> = input.program.chunks(2).collect();
+    if instructions.last() != Some(&&[3, 0][..])
+        || instructions.iter().filter(|i| i[0] == 0).collect_vec() != [&[0, 3]]
+    {
+        anyhow::bail!("The program isn't a loop that shifts A by 3 bits per iteration");
+    }
+    // every digit is 3 bits of `a`.
+    if input.program.len() > 21 {
+        anyhow::bail!("The program is too long for A to fit into 64 bits");
+    }
+
+    search(input, 0, input.program.len() - 1)
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    match find_quine(input)? {
+        Some(a) => Ok(a.to_string()),
+        None => anyhow::bail!("No value of A makes the program output itself"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+    const QUINE_EXAMPLE: &str = "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(part2(&parse(QUINE_EXAMPLE).unwrap()).unwrap(), "117440");
+        assert!(part2(&input).is_err());
+    }
+
+    #[test]
+    fn instructions() {
+        assert_eq!(run(&[5, 0, 5, 1, 5, 4], 10, 0, 0).unwrap(), [0, 1, 2]);
+        let out = run(&[0, 1, 5, 4, 3, 0], 2024, 0, 0).unwrap();
+        assert_eq!(out, [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        // bst with register c, then output b.
+        assert_eq!(run(&[2, 6, 5, 5], 0, 0, 9).unwrap(), [1]);
+        // bxl and bxc.
+        assert_eq!(run(&[1, 7, 5, 5], 0, 29, 0).unwrap(), [26 & 7]);
+        assert_eq!(run(&[4, 0, 5, 5], 0, 2024, 43690).unwrap(), [(2024 ^ 43690) as u8 & 7]);
+        assert!(run(&[5, 7], 0, 0, 0).is_err());
+        assert!(run(&[3, 0], 1, 0, 0).is_err());
+    }
+}
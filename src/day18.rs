@@ -1,8 +1,164 @@
+use std::collections::VecDeque;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use anyhow::Context;
+
+use crate::{
+    grid::{BitGrid, Grid, Pos},
+    parse::Source,
+};
+
+pub const NAME: &str = "RAM Run";
+pub const IMPLEMENTED: bool = true;
+
+// the size of the real memory space and how many bytes fall for part 1.
+// the example uses a 7x7 grid and 12 bytes.
+const SIZE: usize = 71;
+const FALLEN: usize = 1024;
+
+pub struct Input {
+    bytes: Vec<Pos>,
+    // the width and height of the memory space, and how many bytes fall for
+    // part 1. they default to the real input's.
+    pub size: usize,
+    pub fallen: usize,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let bytes = src
+        .lines()
+        .map(|l| {
+            let (x, y) = src.split_once(l, ",")?;
+            Ok((src.num(x)?, src.num(y)?))
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input {
+        bytes,
+        size: SIZE,
+        fallen: FALLEN,
+    })
+}
+
+// the fewest steps from the top left to the bottom right corner of the grid,
+// avoiding all `corrupted` tiles.
+fn shortest_path(corrupted: &BitGrid) -> Option<usize> {
+    let exit = (corrupted.width() - 1, corrupted.height() - 1);
+    let mut dist = Grid::new(corrupted.width(), corrupted.height(), usize::MAX);
+    let mut queue = VecDeque::from([(0, 0)]);
+    if corrupted.at((0, 0)) {
+        return None;
+    }
+    dist[(0, 0)] = 0;
+
+    while let Some(pos) = queue.pop_front() {
+        let d = dist[pos];
+        if pos == exit {
+            return Some(d);
+        }
+        for next in corrupted.neighbours(pos) {
+            if !corrupted.at(next) && dist[next] == usize::MAX {
+                dist[next] = d + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn corrupted(bytes: &[Pos], size: usize) -> anyhow::Result<BitGrid> {
+    if size == 0 {
+        anyhow::bail!("The memory space is empty");
+    }
+    let mut corrupted = BitGrid::new(size, size, false);
+    for &(x, y) in bytes {
+        if !corrupted.contains((x, y)) {
+            anyhow::bail!("Byte {x},{y} is outside of the {size}x{size} memory space");
+        }
+        corrupted.set((x, y), true);
+    }
+    Ok(corrupted)
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+fn steps_after(input: &Input) -> anyhow::Result<usize> {
+    let fallen = input.fallen.min(input.bytes.len());
+    let corrupted = corrupted(&input.bytes[..fallen], input.size)?;
+    shortest_path(&corrupted).context("The exit is unreachable")
+}
+
+// whether the exit is still reachable only depends on how many bytes fell, and once
+// it's cut off it stays that way, so the first blocking byte can be binary searched.
+fn first_blocking_byte(input: &Input) -> anyhow::Result<Pos> {
+    let blocked = |n: usize| -> anyhow::Result<bool> {
+        Ok(shortest_path(&corrupted(&input.bytes[..n], input.size)?).is_none())
+    };
+    if !blocked(input.bytes.len())? {
+        anyhow::bail!("The exit stays reachable after all bytes fell");
+    }
+
+    // the exit is reachable after `lo` bytes, but not after `hi` bytes.
+    let (mut lo, mut hi) = (0, input.bytes.len());
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if blocked(mid)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Ok(input.bytes[hi - 1])
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(steps_after(input)?.to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let (x, y) = first_blocking_byte(input)?;
+    Ok(format!("{x},{y}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    #[test]
+    fn example() {
+        let mut input = parse(EXAMPLE).unwrap();
+        (input.size, input.fallen) = (7, 12);
+        assert_eq!(part1(&input).unwrap(), "22");
+        assert_eq!(part2(&input).unwrap(), "6,1");
+        input.fallen = 25;
+        assert!(part1(&input).is_err());
+        input.size = 0;
+        assert!(part1(&input).is_err());
+    }
+}
@@ -1,8 +1,149 @@
+use crate::parse::Source;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Linen Layout";
+pub const IMPLEMENTED: bool = true;
+
+// a trie of all towel patterns. there are only five colors, so every node
+// just stores the indices of all its children.
+struct Trie {
+    children: Vec<[u32; 5]>,
+    terminal: Vec<bool>,
+}
+
+impl Trie {
+    fn new() -> Trie {
+        Trie { children: vec![[0; 5]], terminal: vec![false] }
+    }
+
+    fn insert(&mut self, pattern: &[u8]) {
+        // 0 is the root, so it can double as "no child".
+        let mut node = 0;
+        for &c in pattern {
+            if self.children[node][c as usize] == 0 {
+                self.children[node][c as usize] = self.children.len() as u32;
+                self.children.push([0; 5]);
+                self.terminal.push(false);
+            }
+            node = self.children[node][c as usize] as usize;
+        }
+        self.terminal[node] = true;
+    }
+}
+
+pub struct Input {
+    patterns: Trie,
+    designs: Vec<Vec<u8>>,
+}
+
+fn parse_colors<'a>(src: &Source<'a>, s: &'a str) -> anyhow::Result<Vec<u8>> {
+    s.bytes()
+        .enumerate()
+        .map(|(i, b)| match b {
+            b'w' => Ok(0),
+            b'u' => Ok(1),
+            b'b' => Ok(2),
+            b'r' => Ok(3),
+            b'g' => Ok(4),
+            _ => Err(src.error(&s[i..], "one of 'w', 'u', 'b', 'r' or 'g'")),
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut lines = src.lines();
+    let first = src.next(&mut lines, input, "a list of towel patterns")?;
+
+    let mut patterns = Trie::new();
+    for pattern in first.split(", ") {
+        if pattern.is_empty() {
+            return Err(src.error(pattern, "a towel pattern"));
+        }
+        patterns.insert(&parse_colors(&src, pattern)?);
+    }
+
+    let designs = lines
+        .filter(|l| !l.is_empty())
+        .map(|l| parse_colors(&src, l))
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input { patterns, designs })
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+// the number of ways `design` can be made from the available patterns. `ways[i]`
+// is the number of ways to make the first `i` stripes, and every way of making
+// a prefix can be extended by every pattern the rest of the design starts with.
+fn arrangements(patterns: &Trie, design: &[u8]) -> u64 {
+    let mut ways = vec![0u64; design.len() + 1];
+    ways[0] = 1;
+
+    for start in 0..design.len() {
+        if ways[start] == 0 {
+            continue;
+        }
+        let mut node = 0;
+        for (end, &c) in design.iter().enumerate().skip(start) {
+            node = patterns.children[node][c as usize] as usize;
+            if node == 0 {
+                break;
+            }
+            if patterns.terminal[node] {
+                ways[end + 1] += ways[start];
+            }
+        }
+    }
+
+    ways[design.len()]
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .designs
+        .iter()
+        .filter(|d| arrangements(&input.patterns, d) > 0)
+        .count()
+        .to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .designs
+        .iter()
+        .map(|d| arrangements(&input.patterns, d))
+        .sum::<u64>()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "6");
+        assert_eq!(part2(&input).unwrap(), "16");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("r, wx\n\nrw\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: expected one of 'w', 'u', 'b', 'r' or 'g', found \"x\""
+        );
+    }
+}
@@ -1,21 +1,32 @@
 use itertools::Itertools;
 use std::cmp::Reverse;
 
+use crate::parse::Source;
+
+pub const NAME: &str = "Red-Nosed Reports";
+pub const IMPLEMENTED: bool = true;
+
 struct Report {
     levels: Vec<usize>,
 }
 
-fn parse_report(line: &str) -> Report {
-    Report {
+fn parse_report<'a>(src: &Source<'a>, line: &'a str) -> anyhow::Result<Report> {
+    Ok(Report {
         levels: line
             .split_ascii_whitespace()
-            .map(|i| i.parse().unwrap())
-            .collect(),
-    }
+            .map(|i| src.num(i))
+            .collect::<anyhow::Result<_>>()?,
+    })
 }
 
-fn parse_input(input: &str) -> Vec<Report> {
-    input.lines().map(parse_report).collect()
+pub struct Input {
+    reports: Vec<Report>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let reports = src.lines().map(|line| parse_report(&src, line)).collect::<anyhow::Result<_>>()?;
+    Ok(Input { reports })
 }
 
 fn is_good(seq: impl Iterator<Item = usize> + Clone) -> bool {
@@ -25,23 +36,54 @@ fn is_good(seq: impl Iterator<Item = usize> + Clone) -> bool {
             .all(|(a, b)| (1..=3).contains(&a.abs_diff(b)))
 }
 
-pub fn part1(input: &str) -> String {
-    let reports = parse_input(input);
-    reports
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .reports
         .iter()
         .filter(|r| is_good(r.levels.iter().copied()))
         .count()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let reports = parse_input(input);
-    reports
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .reports
         .iter()
         .filter(|r| {
             (0..r.levels.len())
                 .any(|i| is_good(r.levels[..i].iter().chain(&r.levels[i + 1..]).copied()))
         })
         .count()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "2");
+        assert_eq!(part2(&input).unwrap(), "4");
+    }
+
+    #[test]
+    fn good_sequences() {
+        assert!(is_good([1, 2, 5, 8].into_iter()));
+        assert!(is_good([8, 5, 2, 1].into_iter()));
+        assert!(is_good([4].into_iter()));
+        assert!(!is_good([1, 1, 2].into_iter()));
+        assert!(!is_good([1, 5, 6].into_iter()));
+        assert!(!is_good([1, 3, 2].into_iter()));
+    }
 }
@@ -1,8 +1,132 @@
+use std::collections::VecDeque;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::grid::{Grid, Pos};
+
+pub const NAME: &str = "Race Condition";
+pub const IMPLEMENTED: bool = true;
+
+// how many picoseconds a cheat has to save to count. the example saves a lot less.
+const THRESHOLD: u32 = 100;
+
+pub struct Input {
+    walls: Grid<bool>,
+    start: Pos,
+    end: Pos,
+    // how many picoseconds a cheat has to save to count, which defaults to the
+    // real input's.
+    pub threshold: u32,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let expected = "'.', '#' or a single 'S' and 'E'";
+    let markers = [(b'S', false), (b'E', false)];
+    let (walls, [start, end]) = Grid::parse_with_markers(input, expected, markers, |_, b| {
+        (b == b'.' || b == b'#').then_some(b == b'#')
+    })?;
+    Ok(Input {
+        walls,
+        start,
+        end,
+        threshold: THRESHOLD,
+    })
+}
+
+impl Input {
+    // the distance from `from` to every tile, without cheating. walls and
+    // unreachable tiles are left at `u32::MAX`.
+    fn distances(&self, from: Pos) -> Grid<u32> {
+        let mut dist = Grid::new(self.walls.width(), self.walls.height(), u32::MAX);
+        let mut queue = VecDeque::from([from]);
+        dist[from] = 0;
+
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[pos];
+            for next in self.walls.neighbours(pos) {
+                if !self.walls.at(next) && dist[next] == u32::MAX {
+                    dist[next] = d + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        dist
+    }
+}
+
+// counts the cheats of at most `max_len` picoseconds that save at least the threshold.
+// a cheat is just a pair of track tiles within `max_len` of each other, and it's
+// as fast as going from the start to the first one, cheating over to the second
+// one in a straight line and going from there to the end.
+fn count_cheats(input: &Input, max_len: isize) -> anyhow::Result<usize> {
+    let threshold = input.threshold;
+    let from_start = input.distances(input.start);
+    let to_end = input.distances(input.end);
+    let best = from_start[input.end];
+    if best == u32::MAX {
+        anyhow::bail!("The end of the track is unreachable");
+    }
+
+    let mut count = 0;
+    for (pos, ds) in from_start.iter() {
+        if ds == u32::MAX {
+            continue;
+        }
+        for dy in -max_len..=max_len {
+            let rest = max_len - dy.abs();
+            for dx in -rest..=rest {
+                let Some(other) = to_end.offset(pos, (dx, dy)) else {
+                    continue;
+                };
+                let de = to_end[other];
+                let len = (dx.abs() + dy.abs()) as u32;
+                if de != u32::MAX && ds + len + de + threshold <= best {
+                    count += 1;
+                }
+            }
+        }
+    }
+    Ok(count)
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(count_cheats(input, 2)?.to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(count_cheats(input, 20)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn example() {
+        let mut input = parse(EXAMPLE).unwrap();
+        for (threshold, part1_cheats) in [(64, "1"), (20, "5"), (1, "44")] {
+            input.threshold = threshold;
+            assert_eq!(part1(&input).unwrap(), part1_cheats);
+        }
+        for (threshold, part2_cheats) in [(76, "3"), (50, "285")] {
+            input.threshold = threshold;
+            assert_eq!(part2(&input).unwrap(), part2_cheats);
+        }
+    }
+}
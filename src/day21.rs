@@ -1,8 +1,178 @@
+use arrayvec::ArrayVec;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::parse::Source;
+
+pub const NAME: &str = "Keypad Conundrum";
+pub const IMPLEMENTED: bool = true;
+
+// the directional keypad. keys are referred to by their index in here.
+//     +---+---+
+//     | ^ | A |
+// +---+---+---+
+// | < | v | > |
+// +---+---+---+
+const DIR_KEYS: [(usize, usize); 5] = [(1, 0), (2, 0), (0, 1), (1, 1), (2, 1)];
+const DIR_GAP: (usize, usize) = (0, 0);
+const UP: usize = 0;
+const ACTIVATE: usize = 1;
+const LEFT: usize = 2;
+const DOWN: usize = 3;
+const RIGHT: usize = 4;
+
+// the numeric keypad. the gap is in the bottom left corner.
+const NUM_GAP: (usize, usize) = (0, 3);
+
+fn num_key(b: u8) -> Option<(usize, usize)> {
+    match b {
+        b'7'..=b'9' => Some(((b - b'7') as usize, 0)),
+        b'4'..=b'6' => Some(((b - b'4') as usize, 1)),
+        b'1'..=b'3' => Some(((b - b'1') as usize, 2)),
+        b'0' => Some((1, 3)),
+        b'A' => Some((2, 3)),
+        _ => None,
+    }
+}
+
+struct Code {
+    keys: Vec<(usize, usize)>,
+    value: u64,
+}
+
+pub struct Input {
+    codes: Vec<Code>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let codes = src
+        .lines()
+        .map(|l| {
+            let keys = (l.bytes().enumerate())
+                .map(|(i, b)| num_key(b).ok_or_else(|| src.error(&l[i..], "a digit or 'A'")))
+                .collect::<anyhow::Result<_>>()?;
+            let digits = l.trim_end_matches('A');
+            let value = if digits.is_empty() { 0 } else { src.num(digits)? };
+            Ok(Code { keys, value })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input { codes })
+}
+
+// the directional keys to press for moving an arm from `from` to `to` and pressing
+// the key there. zigzagging is never better than doing all horizontal moves in one
+// go, so there are at most two options, minus the one that goes over the gap.
+fn paths(
+    from: (usize, usize),
+    to: (usize, usize),
+    gap: (usize, usize),
+) -> impl Iterator<Item = ArrayVec<usize, 6>> {
+    let horizontal = if to.0 < from.0 { LEFT } else { RIGHT };
+    let vertical = if to.1 < from.1 { UP } else { DOWN };
+    let (h, v) = (from.0.abs_diff(to.0), from.1.abs_diff(to.1));
+
+    let path = move |horizontal_first: bool| {
+        let mut keys = ArrayVec::new();
+        let moves = [(horizontal, h), (vertical, v)];
+        for (key, n) in if horizontal_first { moves } else { [moves[1], moves[0]] } {
+            keys.extend(std::iter::repeat_n(key, n));
+        }
+        keys.push(ACTIVATE);
+        keys
+    };
+
+    let horizontal_first = ((to.0, from.1) != gap).then(|| path(true));
+    // if it's a straight line, both options are the same.
+    let vertical_first = ((from.0, to.1) != gap && h != 0 && v != 0).then(|| path(false));
+    horizontal_first.into_iter().chain(vertical_first)
+}
+
+// `costs[a][b]` is how many presses on the outermost keypad it takes to move an arm
+// on a directional keypad from `a` to `b` and press it.
+type Costs = [[u64; 5]; 5];
+
+// the cheapest way to move from `from` to `to` and press it, if every key on the
+// keypad controlling this one costs `costs`. the controlling arm starts and ends
+// on `A`, since it just pressed the previous key and has to press this one.
+fn move_cost(from: (usize, usize), to: (usize, usize), gap: (usize, usize), costs: &Costs) -> u64 {
+    paths(from, to, gap)
+        .map(|keys| {
+            let mut prev = ACTIVATE;
+            keys.iter()
+                .map(|&key| {
+                    let cost = costs[prev][key];
+                    prev = key;
+                    cost
+                })
+                .sum()
+        })
+        .min()
+        .unwrap()
+}
+
+// the costs for an arm that's controlled through `robots` layers of directional
+// keypads. the cost of every layer only depends on the one below it, so they're
+// built bottom up, starting with the keypad that's pressed directly.
+fn layer_costs(robots: usize) -> Costs {
+    let mut costs = [[1; 5]; 5];
+    for _ in 0..robots {
+        costs = std::array::from_fn(|a| {
+            std::array::from_fn(|b| move_cost(DIR_KEYS[a], DIR_KEYS[b], DIR_GAP, &costs))
+        });
+    }
+    costs
+}
+
+fn code_cost(code: &Code, costs: &Costs) -> u64 {
+    let mut prev = (2, 3);
+    code.keys
+        .iter()
+        .map(|&key| {
+            let cost = move_cost(prev, key, NUM_GAP, costs);
+            prev = key;
+            cost
+        })
+        .sum()
+}
+
+fn complexity_sum(input: &Input, robots: usize) -> u64 {
+    let costs = layer_costs(robots);
+    input.codes.iter().map(|c| code_cost(c, &costs) * c.value).sum()
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(complexity_sum(input, 2).to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(complexity_sum(input, 25).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "126384");
+        assert_eq!(part2(&input).unwrap(), "154115708116294");
+    }
+
+    #[test]
+    fn layers() {
+        let input = parse("029A").unwrap();
+        let lens = [0, 1, 2].map(|robots| code_cost(&input.codes[0], &layer_costs(robots)));
+        // <A^A>^^AvvvA, v<<A>>^A<A>AvA<^AA>A<vAAA>^A and the 68 presses from the example.
+        assert_eq!(lens, [12, 28, 68]);
+        assert_eq!(input.codes[0].value, 29);
+    }
+}
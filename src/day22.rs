@@ -1,8 +1,88 @@
+use crate::parse::Source;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Monkey Market";
+pub const IMPLEMENTED: bool = true;
+
+const ROUNDS: usize = 2000;
+// price changes are in -9..=9, so every window of four of them fits into a
+// base 19 number below this.
+const WINDOWS: usize = 19 * 19 * 19 * 19;
+
+pub struct Input {
+    secrets: Vec<u32>,
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let secrets = src.lines().map(|l| src.num(l)).collect::<anyhow::Result<_>>()?;
+    Ok(Input { secrets })
+}
+
+// every step mixes in a shifted copy of the secret and prunes it to 24 bits, so
+// this is just a xorshift generator.
+fn next(mut s: u32) -> u32 {
+    const MASK: u32 = (1 << 24) - 1;
+    s = (s ^ (s << 6)) & MASK;
+    s ^= s >> 5;
+    (s ^ (s << 11)) & MASK
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .secrets
+        .iter()
+        .map(|&s| (0..ROUNDS).fold(s, |s, _| next(s)) as u64)
+        .sum::<u64>()
+        .to_string())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    // the total number of bananas every window of changes would get us.
+    let mut bananas = vec![0u32; WINDOWS];
+    // the last buyer that had each window, since only the first time counts.
+    // this saves clearing a set for every buyer.
+    let mut seen = vec![usize::MAX; WINDOWS];
+
+    for (buyer, &secret) in input.secrets.iter().enumerate() {
+        let mut s = secret;
+        let mut window = 0;
+        for round in 0..ROUNDS {
+            let next = next(s);
+            let (price, prev) = (next % 10, s % 10);
+            window = (window * 19 + (price + 9 - prev) as usize) % WINDOWS;
+            s = next;
+
+            // the first three changes don't make up a whole window yet.
+            if round >= 3 && seen[window] != buyer {
+                seen[window] = buyer;
+                bananas[window] += price;
+            }
+        }
+    }
+
+    Ok(bananas.iter().max().unwrap().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = parse("1\n10\n100\n2024\n").unwrap();
+        assert_eq!(part1(&input).unwrap(), "37327623");
+
+        let input = parse("1\n2\n3\n2024\n").unwrap();
+        assert_eq!(part2(&input).unwrap(), "23");
+    }
+
+    #[test]
+    fn secret_sequence() {
+        let mut s = 123;
+        let expected = [15887950, 16495136, 527345, 704524, 1553684];
+        for e in expected {
+            s = next(s);
+            assert_eq!(s, e);
+        }
+    }
+}
@@ -1,8 +1,196 @@
+use itertools::Itertools;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::parse::Source;
+
+pub const NAME: &str = "LAN Party";
+pub const IMPLEMENTED: bool = true;
+
+// computers are named by two lowercase letters, so they get dense ids below 26².
+// like in day 5, subsets of them are bitsets, just with more than one word.
+const NODES: usize = 26 * 26;
+const WORDS: usize = NODES.div_ceil(64);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Set([u64; WORDS]);
+
+impl Set {
+    const EMPTY: Set = Set([0; WORDS]);
+
+    fn insert(&mut self, n: usize) {
+        self.0[n / 64] |= 1 << (n % 64);
+    }
+
+    fn remove(&mut self, n: usize) {
+        self.0[n / 64] &= !(1 << (n % 64));
+    }
+
+    fn and(&self, other: &Set) -> Set {
+        Set(std::array::from_fn(|i| self.0[i] & other.0[i]))
+    }
+
+    fn and_not(&self, other: &Set) -> Set {
+        Set(std::array::from_fn(|i| self.0[i] & !other.0[i]))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    // all elements, in ascending order.
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &w)| {
+            let mut w = w;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    None
+                } else {
+                    let idx = w.trailing_zeros() as usize;
+                    w &= w - 1;
+                    Some(i * 64 + idx)
+                }
+            })
+        })
+    }
+}
+
+pub struct Input {
+    nodes: Set,
+    adj: Vec<Set>,
+}
+
+fn parse_node<'a>(src: &Source<'a>, s: &'a str) -> anyhow::Result<usize> {
+    match s.as_bytes() {
+        &[a @ b'a'..=b'z', b @ b'a'..=b'z'] => Ok((a - b'a') as usize * 26 + (b - b'a') as usize),
+        _ => Err(src.error(s, "a two letter computer name")),
+    }
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+fn name(n: usize) -> String {
+    [(n / 26) as u8 + b'a', (n % 26) as u8 + b'a'].map(char::from).iter().collect()
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut nodes = Set::EMPTY;
+    let mut adj = vec![Set::EMPTY; NODES];
+
+    for line in src.lines() {
+        let (a, b) = src.split_once(line, "-")?;
+        let (a, b) = (parse_node(&src, a)?, parse_node(&src, b)?);
+        nodes.insert(a);
+        nodes.insert(b);
+        adj[a].insert(b);
+        adj[b].insert(a);
+    }
+    // `aa-aa` doesn't connect anything, but would confuse the clique search.
+    for (n, set) in adj.iter_mut().enumerate() {
+        set.remove(n);
+    }
+
+    Ok(Input { nodes, adj })
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let is_t = |n: usize| n / 26 == (b't' - b'a') as usize;
+    let mut count = 0;
+    // every triangle is only counted once, from its smallest node.
+    for a in input.nodes.iter() {
+        for b in input.adj[a].iter().filter(|&b| b > a) {
+            count += input.adj[a]
+                .and(&input.adj[b])
+                .iter()
+                .filter(|&c| c > b && (is_t(a) || is_t(b) || is_t(c)))
+                .count();
+        }
+    }
+    Ok(count.to_string())
+}
+
+// bron-kerbosch with pivoting. `r` is the current clique, `p` are the nodes that
+// could still be added to it and `x` are the ones that were already tried.
+fn max_clique(adj: &[Set], r: &mut Vec<usize>, mut p: Set, mut x: Set, best: &mut Vec<usize>) {
+    if p.is_empty() {
+        if x.is_empty() && r.len() > best.len() {
+            best.clone_from(r);
+        }
+        return;
+    }
+    // every maximal clique contains either the pivot or one of its non-neighbours,
+    // so picking the pivot with the most neighbours in `p` leaves the fewest branches.
+    let pivot = p.iter().chain(x.iter()).max_by_key(|&u| p.and(&adj[u]).len()).unwrap();
+    for v in p.and_not(&adj[pivot]).iter() {
+        r.push(v);
+        max_clique(adj, r, p.and(&adj[v]), x.and(&adj[v]), best);
+        r.pop();
+        p.remove(v);
+        x.insert(v);
+    }
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let mut best = vec![];
+    max_clique(&input.adj, &mut vec![], input.nodes, Set::EMPTY, &mut best);
+    // ids are ordered the same way as the names.
+    best.sort_unstable();
+    Ok(best.into_iter().map(name).join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "7");
+        assert_eq!(part2(&input).unwrap(), "co,de,ka,ta");
+    }
+
+    #[test]
+    fn names() {
+        let text = "zz";
+        let src = Source::new(text);
+        assert_eq!(name(parse_node(&src, text).unwrap()), "zz");
+        assert_eq!(name(parse_node(&src, text).unwrap() - 1), "zy");
+        assert!(parse_node(&src, &text[1..]).is_err());
+    }
+}
@@ -1,8 +1,267 @@
+use ahash::AHashMap;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+use crate::parse::Source;
+
+pub const NAME: &str = "Crossed Wires";
+pub const IMPLEMENTED: bool = true;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    And,
+    Or,
+    Xor,
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+struct Gate {
+    a: usize,
+    b: usize,
+    op: Op,
+    out: usize,
+}
+
+pub struct Input {
+    // wires are referred to by their index in here.
+    names: Vec<String>,
+    initial: Vec<Option<bool>>,
+    gates: Vec<Gate>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut lines = src.lines();
+    let mut ids = AHashMap::new();
+    let mut names = vec![];
+    let mut wire = |s: &str| {
+        *ids.entry(s.to_string()).or_insert_with(|| {
+            names.push(s.to_string());
+            names.len() - 1
+        })
+    };
+
+    let mut initial = vec![];
+    for line in lines.by_ref().take_while(|l| !l.is_empty()) {
+        let (name, value) = src.split_once(line, ": ")?;
+        let value = match value {
+            "0" => false,
+            "1" => true,
+            _ => return Err(src.error(value, "'0' or '1'")),
+        };
+        let id = wire(name);
+        initial.resize(initial.len().max(id + 1), None);
+        initial[id] = Some(value);
+    }
+
+    let mut gates = vec![];
+    for line in lines {
+        let (lhs, out) = src.split_once(line, " -> ")?;
+        let mut words = lhs.split(' ');
+        let a = src.next(&mut words, lhs, "a wire")?;
+        let op = src.next(&mut words, lhs, "a gate")?;
+        let op = match op {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "XOR" => Op::Xor,
+            _ => return Err(src.error(op, "one of AND, OR or XOR")),
+        };
+        let b = src.next(&mut words, lhs, "a wire")?;
+        if let Some(extra) = words.next() {
+            return Err(src.error(extra, "\" -> \""));
+        }
+        gates.push(Gate { a: wire(a), b: wire(b), op, out: wire(out) });
+    }
+
+    initial.resize(names.len(), None);
+    Ok(Input { names, initial, gates })
+}
+
+// evaluates every gate once all of its inputs are known, i.e. in topological order.
+fn simulate(input: &Input) -> anyhow::Result<Vec<Option<bool>>> {
+    let mut values = input.initial.clone();
+    // the gates reading each wire, and how many unknown inputs each gate still has.
+    let mut readers = vec![vec![]; values.len()];
+    let mut missing = vec![0; input.gates.len()];
+    let mut ready = vec![];
+
+    for (i, g) in input.gates.iter().enumerate() {
+        for w in [g.a, g.b] {
+            readers[w].push(i);
+            missing[i] += values[w].is_none() as usize;
+        }
+        if missing[i] == 0 {
+            ready.push(i);
+        }
+    }
+
+    while let Some(i) = ready.pop() {
+        let g = &input.gates[i];
+        let (a, b) = (values[g.a].unwrap(), values[g.b].unwrap());
+        if values[g.out].is_some() {
+            anyhow::bail!("Wire {} has more than one value", input.names[g.out]);
+        }
+        values[g.out] = Some(match g.op {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        });
+        for &r in &readers[g.out] {
+            missing[r] -= 1;
+            if missing[r] == 0 {
+                ready.push(r);
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let values = simulate(input)?;
+    let mut z = 0u64;
+    for (name, value) in input.names.iter().zip(&values) {
+        let Some(bit) = name.strip_prefix('z') else {
+            continue;
+        };
+        let Ok(bit @ 0..64) = bit.parse::<u32>() else {
+            anyhow::bail!("Output wire {name} doesn't fit into 64 bits");
+        };
+        match value {
+            Some(v) => z |= (*v as u64) << bit,
+            None => anyhow::bail!("Output wire {name} never gets a value"),
+        }
+    }
+    Ok(z.to_string())
+}
+
+// the circuit is supposed to be a ripple-carry adder, where every bit `i` is
+//
+//     x XOR y -> sum          x AND y -> carry1
+//     sum XOR carry_in -> z   sum AND carry_in -> carry2
+//     carry1 OR carry2 -> carry_out
+//
+// except for bit 0, which is a half adder, and the highest z, which is the final
+// carry. a wire is reported as swapped if its gate doesn't fit into this pattern.
+fn swapped_wires(input: &Input) -> Vec<usize> {
+    let name = |w: usize| input.names[w].as_str();
+    let is_z = |w: usize| name(w).starts_with('z');
+    let is_xy = |w: usize| name(w).starts_with(['x', 'y']);
+    let last_z = (0..input.names.len()).filter(|&w| is_z(w)).max_by_key(|&w| name(w));
+
+    let mut readers = vec![vec![]; input.names.len()];
+    for g in &input.gates {
+        readers[g.a].push(g.op);
+        readers[g.b].push(g.op);
+    }
+
+    let mut swapped = vec![];
+    for g in &input.gates {
+        let from_xy = is_xy(g.a) && is_xy(g.b);
+        let first_bit = from_xy && name(g.a)[1..] == *"00";
+        let feeds = |op: Op| readers[g.out].contains(&op);
+
+        let wrong = match g.op {
+            // all outputs are sums, except for the final carry. the sums also take
+            // the carry into account, except for bit 0.
+            _ if Some(g.out) == last_z => g.op != Op::Or,
+            _ if is_z(g.out) => g.op != Op::Xor || (from_xy && !first_bit),
+            // the only xors besides the sums take the inputs and feed into one.
+            Op::Xor if !from_xy => true,
+            Op::Xor => !first_bit && !feeds(Op::Xor),
+            // every carry half is part of a carry out, except for bit 0, where
+            // it's the carry out itself.
+            Op::And => !first_bit && !feeds(Op::Or),
+            Op::Or => false,
+        };
+        if wrong {
+            swapped.push(g.out);
+        }
+    }
+    swapped
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let swapped = swapped_wires(input);
+    let mut names: Vec<_> = swapped.into_iter().map(|w| input.names[w].as_str()).collect();
+    names.sort_unstable();
+    if names.len() != 8 {
+        anyhow::bail!(
+            "Expected 4 swapped pairs of wires, but found {} suspicious ones: {}",
+            names.len(),
+            names.join(",")
+        );
+    }
+    Ok(names.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02
+";
+
+    // an adder of `bits` bits, with the given pairs of wires swapped.
+    fn adder(bits: usize, x: u64, y: u64, swaps: &[(&str, &str)]) -> String {
+        let mut text = String::new();
+        for (c, n) in [('x', x), ('y', y)] {
+            for i in 0..bits {
+                text += &format!("{c}{i:02}: {}\n", n >> i & 1);
+            }
+        }
+        text += "\n";
+
+        let mut gates = vec![];
+        gates.push(("x00 XOR y00".to_string(), "z00".to_string()));
+        gates.push(("x00 AND y00".to_string(), "c00".to_string()));
+        for i in 1..bits {
+            let carry_out = if i == bits - 1 { format!("z{bits:02}") } else { format!("c{i:02}") };
+            gates.extend([
+                (format!("x{i:02} XOR y{i:02}"), format!("a{i:02}")),
+                (format!("x{i:02} AND y{i:02}"), format!("b{i:02}")),
+                (format!("a{i:02} XOR c{:02}", i - 1), format!("z{i:02}")),
+                (format!("a{i:02} AND c{:02}", i - 1), format!("d{i:02}")),
+                (format!("b{i:02} OR d{i:02}"), carry_out),
+            ]);
+        }
+        for (lhs, mut out) in gates {
+            for &(a, b) in swaps {
+                if out == a {
+                    out = b.to_string();
+                } else if out == b {
+                    out = a.to_string();
+                }
+            }
+            text += &format!("{lhs} -> {out}\n");
+        }
+        text
+    }
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "4");
+    }
+
+    #[test]
+    fn correct_adder() {
+        let input = parse(&adder(8, 173, 98, &[])).unwrap();
+        assert_eq!(part1(&input).unwrap(), "271");
+        assert!(swapped_wires(&input).is_empty());
+    }
+
+    #[test]
+    fn swapped_adder() {
+        let swaps = [("z02", "a02"), ("z03", "c03"), ("a04", "b04"), ("z06", "d06")];
+        let input = parse(&adder(8, 173, 98, &swaps)).unwrap();
+        assert_eq!(part2(&input).unwrap(), "a02,a04,b04,c03,d06,z02,z03,z06");
+    }
+}
@@ -1,8 +1,129 @@
+use crate::parse::Source;
 
-pub fn part1(_input: &str) -> String {
-    todo!()
+pub const NAME: &str = "Code Chronicle";
+pub const IMPLEMENTED: bool = true;
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 7;
+
+// every schematic is a bitmask of its filled cells. a key fits into a lock
+// exactly if none of them overlap.
+pub struct Input {
+    locks: Vec<u64>,
+    keys: Vec<u64>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut lines = src.lines().filter(|l| !l.is_empty());
+    let (mut locks, mut keys) = (vec![], vec![]);
+
+    while let Some(first) = lines.next() {
+        let mut mask = 0u64;
+        for row in 0..HEIGHT {
+            let line = match row {
+                0 => first,
+                _ => src.next(&mut lines, input, format_args!("{HEIGHT} schematic rows"))?,
+            };
+            if line.len() != WIDTH {
+                return Err(src.error(line, format_args!("a row of length {WIDTH}")));
+            }
+            for (col, b) in line.bytes().enumerate() {
+                match b {
+                    b'#' => mask |= 1 << (row * WIDTH + col),
+                    b'.' => {}
+                    _ => return Err(src.error(&line[col..], "'#' or '.'")),
+                }
+            }
+        }
+
+        let full_row = (1 << WIDTH) - 1;
+        if mask & full_row == full_row {
+            locks.push(mask);
+        } else if mask >> ((HEIGHT - 1) * WIDTH) == full_row {
+            keys.push(mask);
+        } else {
+            let expected = "a lock with a full top row or a key with a full bottom row";
+            return Err(src.error(first, expected));
+        }
+    }
+
+    Ok(Input { locks, keys })
 }
 
-pub fn part2(_input: &str) -> String {
-    todo!()
-}
\ No newline at end of file
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let fitting = input
+        .locks
+        .iter()
+        .map(|lock| input.keys.iter().filter(|&key| lock & key == 0).count())
+        .sum::<usize>();
+    Ok(fitting.to_string())
+}
+
+// there's no second puzzle on the last day, just the final star for finishing
+// all the others.
+pub fn part2(_input: &Input) -> anyhow::Result<String> {
+    Ok("Merry Christmas!".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!((input.locks.len(), input.keys.len()), (2, 3));
+        assert_eq!(part1(&input).unwrap(), "3");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("#####\n#####\n").err().unwrap();
+        let expected = "line 3, column 1: expected 7 schematic rows, found end of input";
+        assert_eq!(err.to_string(), expected);
+        assert!(parse(".....\n.....\n.....\n.....\n.....\n.....\n.....\n").is_err());
+    }
+}
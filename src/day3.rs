@@ -1,5 +1,8 @@
 use memchr::memchr2_iter;
 
+pub const NAME: &str = "Mull It Over";
+pub const IMPLEMENTED: bool = true;
+
 enum Command {
     Do,
     Dont,
@@ -37,24 +40,56 @@ fn commands(input: &str) -> impl Iterator<Item = Command> {
     })
 }
 
-pub fn part1(input: &str) -> String {
-    commands(input)
+pub struct Input {
+    commands: Vec<Command>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    Ok(Input { commands: commands(input).collect() })
+}
+
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .commands
+        .iter()
         .filter_map(|cmd| match cmd {
-            Command::Mul { lhs, rhs } => Some(lhs * rhs),
+            &Command::Mul { lhs, rhs } => Some(lhs * rhs),
             _ => None,
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    commands(input)
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .commands
+        .iter()
         .fold((0usize, true), |(sum, enabled), cmd| match cmd {
             Command::Do => (sum, true),
             Command::Dont => (sum, false),
             Command::Mul { .. } if !enabled => (sum, enabled),
-            Command::Mul { lhs, rhs } => (sum + lhs * rhs, enabled),
+            &Command::Mul { lhs, rhs } => (sum + lhs * rhs, enabled),
         })
         .0
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example1 = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let example2 = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(part1(&parse(example1).unwrap()).unwrap(), "161");
+        assert_eq!(part2(&parse(example2).unwrap()).unwrap(), "48");
+    }
+
+    #[test]
+    fn malformed_muls() {
+        let input = parse("mul(1234,5) mul(,5) mul(2,3 mul ( 2,3) mul(2,3)").unwrap();
+        assert_eq!(part1(&input).unwrap(), "6");
+        assert_eq!(part1(&parse("mul(999,999)").unwrap()).unwrap(), "998001");
+    }
 }
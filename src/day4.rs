@@ -1,76 +1,70 @@
-use itertools::Itertools;
 use memchr::memchr_iter;
 
-#[derive(Debug)]
-struct Grid {
-    width: usize,
-    height: usize,
-    data: Vec<u8>,
-}
+use crate::{
+    grid::{Dir8, Grid},
+    parse::Source,
+};
 
-impl Grid {
-    fn parse(input: &str) -> Self {
-        let data = input.lines().flat_map(str::bytes).collect_vec();
-        let width = input.find('\n').unwrap();
-        let height = data.len() / width;
+pub const NAME: &str = "Ceres Search";
+pub const IMPLEMENTED: bool = true;
 
-        Self {
-            width,
-            height,
-            data,
-        }
-    }
+pub type Input = Grid<u8>;
 
-    fn get(&self, x: usize, y: usize) -> Option<u8> {
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            Some(self.data[y * self.width + x])
-        }
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    if src.lines().next().is_none_or(str::is_empty) {
+        return Err(src.error(input, "a non-empty grid"));
     }
+    Grid::parse(input, "a letter", |_, b| Some(b))
 }
 
-pub fn part1(input: &str) -> String {
-    let grid = Grid::parse(input);
-
-    memchr_iter(b'X', &grid.data)
-        .map(|i| (i % grid.width, i / grid.width))
-        .map(|(x, y)| {
-            (-1..=1)
-                .cartesian_product(-1..=1)
-                .filter(|&(dx, dy)| {
-                    (dx, dy) != (0, 0)
-                        && (1..=3)
-                            .map(|i| {
-                                grid.get(
-                                    x.wrapping_add_signed(dx * i),
-                                    y.wrapping_add_signed(dy * i),
-                                )
-                            })
-                            .eq(b"MAS".iter().copied().map(Some))
-                })
-                .count()
-        })
+pub fn part1(grid: &Input) -> anyhow::Result<String> {
+    Ok(memchr_iter(b'X', grid.as_slice())
+        .map(|i| grid.pos(i))
+        .map(|pos| Dir8::ALL.into_iter().filter(|&d| grid.ray(pos, d).take(4).eq(*b"XMAS")).count())
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let grid = Grid::parse(input);
-
-    memchr_iter(b'A', &grid.data)
-        .map(|i| (i % grid.width, i / grid.width))
-        .filter(|(x, y)| {
-            [(-1, -1), (1, -1), (-1, 1), (1, 1)]
-                .into_iter()
-                .map(|(dx, dy)| (dx as usize, dy as usize))
-                .any(|(dx, dy)| {
-                    grid.get(x.wrapping_sub(dx), y.wrapping_sub(dy)) == Some(b'M')
-                        && grid.get(x.wrapping_add(dx), y.wrapping_add(dy)) == Some(b'S')
-                        && grid.get(x.wrapping_add(dy), y.wrapping_sub(dx)) == Some(b'M')
-                        && grid.get(x.wrapping_sub(dy), y.wrapping_add(dx)) == Some(b'S')
-                })
+pub fn part2(grid: &Input) -> anyhow::Result<String> {
+    Ok(memchr_iter(b'A', grid.as_slice())
+        .map(|i| grid.pos(i))
+        .filter(|&pos| {
+            let near = |d: Dir8| grid.step(pos, d).map(|p| grid.at(p));
+            // an "MAS" along the diagonal `d`, crossing the "A" at `pos`.
+            let mas = |d: Dir8| near(d.reverse()) == Some(b'M') && near(d) == Some(b'S');
+            Dir8::DIAGONALS.into_iter().any(|d| mas(d) && mas(d.turn_right().turn_right()))
         })
         .count()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "18");
+        assert_eq!(part2(&input).unwrap(), "9");
+    }
+
+    #[test]
+    fn ragged_grid() {
+        assert!(parse("XMAS\nXM\n").is_err());
+    }
 }
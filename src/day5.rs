@@ -1,5 +1,10 @@
 use smallvec::SmallVec;
 
+use crate::parse::Source;
+
+pub const NAME: &str = "Print Queue";
+pub const IMPLEMENTED: bool = true;
+
 // the graph used to model the goes-before relationships in the rules.
 // the puzzle input only seems to have 2-digit vertex numbers, so we can
 // use u128 bitsets for vertex subsets and a dense 100 element adjacency list.
@@ -10,20 +15,19 @@ struct Graph {
 }
 
 impl Graph {
-    fn parse(input: &str) -> Self {
+    fn parse<'a>(src: &Source<'a>, rules: &'a str) -> anyhow::Result<Self> {
         let mut graph = Self { nodes: 0, out_edges: [0; 100], in_edges: [0; 100] };
 
-        for line in input.lines() {
-            let (a, b) = line.split_once('|').unwrap();
-            let (a, b) = (a.parse::<u8>().unwrap(), b.parse::<u8>().unwrap());
-            assert!(a < 100 && b < 100);
+        for line in rules.lines() {
+            let (a, b) = src.split_once(line, "|")?;
+            let (a, b) = (parse_node(src, a)?, parse_node(src, b)?);
 
             graph.nodes |= (1u128 << a) | (1u128 << b);
             graph.out_edges[a as usize] |= 1u128 << b;
             graph.in_edges[b as usize] |= 1u128 << a;
         }
 
-        graph
+        Ok(graph)
     }
 
     fn subgraph(&self, nodes: u128) -> Graph {
@@ -65,21 +69,36 @@ struct Update {
     node_bitset: u128,
 }
 
-fn parse_updates(input: &str) -> Vec<Update> {
-    input
+// the bitsets only have room for 2-digit vertex numbers.
+fn parse_node<'a>(src: &Source<'a>, s: &'a str) -> anyhow::Result<u8> {
+    match src.num::<u8>(s) {
+        Ok(n) if n < 100 => Ok(n),
+        _ => Err(src.error(s, "a page number below 100")),
+    }
+}
+
+fn parse_updates<'a>(src: &Source<'a>, updates: &'a str) -> anyhow::Result<Vec<Update>> {
+    updates
         .lines()
         .map(|line| {
-            let nodes: SmallVec<_> = line.split(',').map(|s| s.parse::<u8>().unwrap()).collect();
+            let nodes: SmallVec<_> =
+                line.split(',').map(|s| parse_node(src, s)).collect::<anyhow::Result<_>>()?;
             let node_bitset = nodes.iter().fold(0u128, |acc, i| acc | (1u128 << i));
 
-            Update { nodes, node_bitset }
+            Ok(Update { nodes, node_bitset })
         })
         .collect()
 }
 
-fn parse_input(input: &str) -> (Graph, Vec<Update>) {
-    let (g, u) = input.split_once("\n\n").unwrap();
-    (Graph::parse(g), parse_updates(u))
+pub struct Input {
+    graph: Graph,
+    updates: Vec<Update>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let (g, u) = src.split_once(input, "\n\n")?;
+    Ok(Input { graph: Graph::parse(&src, g)?, updates: parse_updates(&src, u)? })
 }
 
 // this is a stupid implementation in O(n^2) but the
@@ -118,25 +137,81 @@ fn sort_topo(update: &Update, g: &Graph) -> Update {
     out
 }
 
-pub fn part1(input: &str) -> String {
-    let (graph, updates) = parse_input(input);
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let Input { graph, updates } = input;
 
-    updates
+    Ok(updates
         .iter()
-        .filter(|u| is_well_ordered(u, &graph))
+        .filter(|u| is_well_ordered(u, graph))
         .map(|u| u.nodes[u.nodes.len() / 2] as usize)
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let (graph, updates) = parse_input(input);
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let Input { graph, updates } = input;
 
-    updates
+    Ok(updates
         .iter()
-        .filter(|u| !is_well_ordered(u, &graph))
-        .map(|u| sort_topo(u, &graph))
+        .filter(|u| !is_well_ordered(u, graph))
+        .map(|u| sort_topo(u, graph))
         .map(|u| u.nodes[u.nodes.len() / 2] as usize)
         .sum::<usize>()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "143");
+        assert_eq!(part2(&input).unwrap(), "123");
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(bit_iter(0).count(), 0);
+        assert!(bit_iter(0b1011).eq([0, 1, 3]));
+        assert!(bit_iter(1 << 127).eq([127]));
+        assert!(bit_iter(u128::MAX).eq(0..128));
+    }
+
+    #[test]
+    fn page_out_of_range() {
+        assert!(parse("47|100\n\n47\n").is_err());
+    }
 }
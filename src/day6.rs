@@ -1,93 +1,65 @@
 // use std::ops::RangeInclusive;
 
 use ahash::AHashSet;
-use bitvec::vec::BitVec;
 use itertools::Itertools;
 
-struct Grid {
-    width: usize,
-    height: usize,
-    matrix: BitVec,
-}
-
-impl Grid {
-    fn parse(input: &str) -> (Grid, (usize, usize)) {
-        let (mut width, mut height) = (0, 0);
-        let mut start_pos = (0, 0);
-        let mut matrix = BitVec::new();
-
-        for line in input.lines() {
-            width = line.len();
-            assert!(width < 256 && height < 256);
-            for (col, b) in line.bytes().enumerate() {
-                if b == b'^' {
-                    start_pos = (col, height);
-                }
-                matrix.push(b == b'#');
-            }
-            height += 1;
-        }
-
-        (
-            Grid {
-                width,
-                height,
-                matrix,
-            },
-            start_pos,
-        )
-    }
+use crate::{
+    grid::{BitGrid, Dir, Direction, Pos},
+    parse::Source,
+};
 
-    fn get(&self, x: usize, y: usize) -> bool {
-        assert!(x < self.width && y < self.height);
-        self.matrix[self.width * y + x]
-    }
+pub const NAME: &str = "Guard Gallivant";
+pub const IMPLEMENTED: bool = true;
 
-    fn set(&mut self, x: usize, y: usize, val: bool) {
-        assert!(x < self.width && y < self.height);
-        self.matrix.set(self.width * y + x, val);
-    }
+pub struct Input {
+    grid: BitGrid,
+    start_pos: Pos,
 }
 
-pub fn part1(input: &str) -> String {
-    let (grid, start_pos) = Grid::parse(input);
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let mut start_pos = None;
+    let grid = BitGrid::parse(input, "'.', '#' or a single '^'", |pos, b| match b {
+        b'^' if start_pos.is_none() => {
+            start_pos = Some(pos);
+            Some(false)
+        }
+        b'.' | b'#' => Some(b == b'#'),
+        _ => None,
+    })?;
 
-    let mut visited = Grid {
-        width: grid.width,
-        height: grid.height,
-        matrix: BitVec::repeat(false, grid.matrix.len()),
-    };
+    let start_pos =
+        start_pos.ok_or_else(|| src.error(src.end_of(input), "a '^' somewhere in the grid"))?;
+    Ok(Input { grid, start_pos })
+}
 
-    visited.set(start_pos.0, start_pos.1, true);
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let Input { ref grid, start_pos } = *input;
 
-    let (mut x, mut y) = start_pos;
-    let (mut dx, mut dy) = (0, -1);
+    let mut visited = BitGrid::new(grid.width(), grid.height(), false);
+    visited.set(start_pos, true);
 
-    loop {
-        // if we walk off the top or left edge, this will overflow but the bounds check
-        // below still works correctly
-        let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-        if nx >= grid.width || ny >= grid.height {
-            break;
-        }
+    let mut pos = start_pos;
+    let mut dir = Dir::Up;
 
-        if grid.get(nx, ny) {
-            (dx, dy) = (-dy, dx);
+    while let Some(next) = grid.step(pos, dir) {
+        if grid.at(next) {
+            dir = dir.turn_right();
             continue;
         }
 
-        visited.set(nx, ny, true);
-        (x, y) = (nx, ny);
+        visited.set(next, true);
+        pos = next;
     }
 
-    visited.matrix.count_ones().to_string()
+    Ok(visited.count_ones().to_string())
 }
 
 #[derive(Debug)]
 struct Segment {
-    start: (usize, usize),
-    dir: (isize, isize),
-    end: (usize, usize),
+    start: Pos,
+    dir: Dir,
+    end: Pos,
 }
 
 impl Segment {
@@ -98,9 +70,9 @@ impl Segment {
     //     )
     // }
 
-    fn coords_iter(&self) -> impl Iterator<Item = (usize, usize)> {
+    fn coords_iter(&self) -> impl Iterator<Item = Pos> {
         let (mut x, mut y) = self.start;
-        let (dx, dy) = self.dir;
+        let (dx, dy) = self.dir.delta();
         let end = self.end;
         (x, y) = (x.wrapping_add_signed(-dx), y.wrapping_add_signed(-dy));
 
@@ -114,97 +86,70 @@ impl Segment {
     }
 }
 
-fn get_segments(grid: &Grid, start_pos: (usize, usize)) -> Vec<Segment> {
+fn get_segments(grid: &BitGrid, start_pos: Pos) -> Vec<Segment> {
     let mut segs = vec![];
 
-    let (mut x, mut y) = start_pos;
-    let mut seg_start = (x, y);
-    let (mut dx, mut dy) = (0, -1);
+    let mut pos = start_pos;
+    let mut seg_start = pos;
+    let mut dir = Dir::Up;
 
     loop {
-        // if we walk off the top or left edge, this will overflow but the bounds check
-        // below still works correctly
-        let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-        if nx >= grid.width || ny >= grid.height {
-            segs.push(Segment {
-                start: seg_start,
-                dir: (dx, dy),
-                end: (x, y),
-            });
+        let Some(next) = grid.step(pos, dir) else {
+            segs.push(Segment { start: seg_start, dir, end: pos });
             break;
-        }
+        };
 
-        if grid.get(nx, ny) {
-            segs.push(Segment {
-                start: seg_start,
-                dir: (dx, dy),
-                end: (x, y),
-            });
-            seg_start = (x, y);
-            (dx, dy) = (-dy, dx);
+        if grid.at(next) {
+            segs.push(Segment { start: seg_start, dir, end: pos });
+            seg_start = pos;
+            dir = dir.turn_right();
             continue;
         }
-        (x, y) = (nx, ny);
+        pos = next;
     }
 
     segs
 }
 
-fn has_loop(grid: &Grid, start_pos: (usize, usize), dir: (isize, isize)) -> bool {
+fn has_loop(grid: &BitGrid, start_pos: Pos, dir: Dir) -> bool {
     let mut visited = AHashSet::new();
     visited.insert((start_pos, dir));
 
-    let (mut x, mut y) = start_pos;
-    let (mut dx, mut dy) = dir;
-
-    loop {
-        // if we walk off the top or left edge, this will overflow but the bounds check
-        // below still works correctly
-        let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-        if nx >= grid.width || ny >= grid.height {
-            return false;
-        }
+    let (mut pos, mut dir) = (start_pos, dir);
 
-        if grid.get(nx, ny) {
-            (dx, dy) = (-dy, dx);
+    while let Some(next) = grid.step(pos, dir) {
+        if grid.at(next) {
+            dir = dir.turn_right();
             continue;
         }
 
-        (x, y) = (nx, ny);
-        if !visited.insert(((x, y), (dx, dy))) {
+        pos = next;
+        if !visited.insert((pos, dir)) {
             return true;
         }
     }
+    false
 }
 
-pub fn part2(input: &str) -> String {
-    let (mut grid, start_pos) = Grid::parse(input);
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let (mut grid, start_pos) = (input.grid.clone(), input.start_pos);
     let segments = get_segments(&grid, start_pos);
 
-    segments
+    Ok(segments
         .iter()
-        .flat_map(|s| {
-            s.coords_iter().map(|(x, y)| {
-                (
-                    x.wrapping_add_signed(s.dir.0),
-                    y.wrapping_add_signed(s.dir.1),
-                )
-            })
-        })
-        .filter(|&(x, y)| {
-            x < grid.width && y < grid.width && (x, y) != start_pos && !grid.get(x, y)
-        })
+        .flat_map(|s| s.coords_iter().filter_map(|pos| grid.step(pos, s.dir)))
+        .filter(|&pos| pos != start_pos && !grid.at(pos))
         .collect_vec()
         .into_iter()
-        .filter(|&(x, y)| {
-            grid.set(x, y, true);
-            let has_loop = has_loop(&grid, start_pos, (0, -1));
-            grid.set(x, y, false);
+        .filter(|&pos| {
+            grid.set(pos, true);
+            let has_loop = has_loop(&grid, start_pos, Dir::Up);
+            grid.set(pos, false);
             has_loop
         })
         .collect::<AHashSet<_>>()
         .len()
-        .to_string()
+        .to_string())
 
     // for (i, seg) in segments.iter().enumerate() {
     //     for prev in &segments[..i] {
@@ -251,5 +196,35 @@ pub fn part2(input: &str) -> String {
     //     }
     // }
 
-    // num_candidates.to_string()
+    // Ok(num_candidates.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "41");
+        assert_eq!(part2(&input).unwrap(), "6");
+    }
+
+    #[test]
+    fn missing_guard() {
+        assert!(parse("..#\n...\n").is_err());
+    }
 }
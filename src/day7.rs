@@ -1,23 +1,36 @@
+use crate::parse::Source;
+
+pub const NAME: &str = "Bridge Repair";
+pub const IMPLEMENTED: bool = true;
+
 struct Row {
     expected: usize,
     values: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> Vec<Row> {
-    input
+pub struct Input {
+    rows: Vec<Row>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
+    let rows = src
         .lines()
         .map(|l| {
-            let (e, v) = l.split_once(": ").unwrap();
-            Row {
-                expected: e.parse().unwrap(),
-                values: v.split(' ').map(|v| v.parse().unwrap()).collect(),
-            }
+            let (e, v) = src.split_once(l, ": ")?;
+            Ok(Row {
+                expected: src.num(e)?,
+                values: v.split(' ').map(|v| src.num(v)).collect::<anyhow::Result<_>>()?,
+            })
         })
-        .collect()
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Input { rows })
 }
 
 fn concat(l: usize, r: usize) -> Option<usize> {
-    let num_digits = r.ilog10() + 1;
+    // 0 still takes up one digit.
+    let num_digits = r.checked_ilog10().unwrap_or(0) + 1;
     l.checked_mul(10usize.checked_pow(num_digits)?)?
         .checked_add(r)
 }
@@ -43,23 +56,56 @@ fn is_possible(r: &Row, allow_concat: bool) -> bool {
     rec(r.expected, Some(0), &r.values, allow_concat)
 }
 
-pub fn part1(input: &str) -> String {
-    let rows = parse_input(input);
-
-    rows.iter()
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .rows
+        .iter()
         .filter(|r| is_possible(r, false))
         .map(|r| r.expected)
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-
-    let rows = parse_input(input);
-
-    rows.iter()
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(input
+        .rows
+        .iter()
         .filter(|r| is_possible(r, true))
         .map(|r| r.expected)
         .sum::<usize>()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "3749");
+        assert_eq!(part2(&input).unwrap(), "11387");
+    }
+
+    #[test]
+    fn concatenation() {
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(0, 7), Some(7));
+        assert_eq!(concat(1, 0), Some(10));
+        assert_eq!(concat(5, 10), Some(510));
+        assert_eq!(concat(usize::MAX / 10, 9), None);
+        assert_eq!(concat(1, usize::MAX), None);
+    }
 }
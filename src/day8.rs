@@ -1,72 +1,61 @@
+use std::iter;
+
 use ahash::AHashSet;
 use itertools::Itertools;
 
-struct Grid {
-    width: usize,
-    height: usize,
+use crate::grid::{Grid, Pos};
+
+pub const NAME: &str = "Resonant Collinearity";
+pub const IMPLEMENTED: bool = true;
+
+pub struct Input {
+    grid: Grid<u8>,
     // dense map from bytes to coord lists. there's some space savings
     // to be had here from only including printable ascii characters,
     // but there's only gonna be one instance of this created anyways
     // so the 6KiB that this uses up shouldn't be an issue.
-    antennas: [Vec<(usize, usize)>; 256],
+    antennas: [Vec<Pos>; 256],
 }
 
-impl Grid {
-    fn parse(input: &str) -> Grid {
-        let (mut width, mut height) = (0, 0);
-        let mut antennas = [const { Vec::new() }; 256];
-
-        for line in input.lines() {
-            width = line.len();
-            for (col, b) in line.bytes().enumerate() {
-                if b != b'.' {
-                    antennas[b as usize].push((col, height));
-                }
-            }
-            height += 1;
-        }
-
-        Grid {
-            width,
-            height,
-            antennas,
-        }
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let grid = Grid::parse(input, "an antenna or '.'", |_, b| Some(b))?;
+    let mut antennas = [const { Vec::new() }; 256];
+    for (pos, b) in grid.iter().filter(|&(_, b)| b != b'.') {
+        antennas[b as usize].push(pos);
     }
+    Ok(Input { grid, antennas })
 }
 
-pub fn part1(input: &str) -> String {
-    let grid = Grid::parse(input);
+// the vector from `a` to `b`.
+fn delta((ax, ay): Pos, (bx, by): Pos) -> (isize, isize) {
+    (bx as isize - ax as isize, by as isize - ay as isize)
+}
 
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    let grid = &input.grid;
     let mut locations = AHashSet::new();
-    for antenna in grid.antennas.iter() {
-        for ((ax, ay), (bx, by)) in antenna.iter().copied().tuple_combinations() {
-            let (dx, dy) = (bx as isize - ax as isize, by as isize - ay as isize);
-
-            for (cx, cy) in [
-                (ax.wrapping_add_signed(-dx), ay.wrapping_add_signed(-dy)),
-                (bx.wrapping_add_signed(dx), by.wrapping_add_signed(dy)),
-            ] {
-                if cx < grid.width && cy < grid.height {
-                    locations.insert((cx, cy));
-                }
-            }
+    for antenna in input.antennas.iter() {
+        for (a, b) in antenna.iter().copied().tuple_combinations() {
+            let (dx, dy) = delta(a, b);
+            locations.extend(grid.offset(a, (-dx, -dy)));
+            locations.extend(grid.offset(b, (dx, dy)));
         }
     }
 
-    locations.len().to_string()
+    Ok(locations.len().to_string())
 }
 
+// always non-negative, regardless of the signs of `a` and `b`.
 fn gcd(a: isize, b: isize) -> isize {
-    if b == 0 { a } else { gcd(b, a % b) }
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
 }
 
-pub fn part2(input: &str) -> String {
-    let grid = Grid::parse(input);
-
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    let grid = &input.grid;
     let mut locations = AHashSet::new();
-    for antenna in grid.antennas.iter() {
-        for ((ax, ay), (bx, by)) in antenna.iter().copied().tuple_combinations() {
-            let (dx, dy) = (bx as isize - ax as isize, by as isize - ay as isize);
+    for antenna in input.antennas.iter() {
+        for (a, b) in antenna.iter().copied().tuple_combinations() {
+            let (dx, dy) = delta(a, b);
             let gcd = gcd(dx, dy);
             let (dx, dy) = (dx / gcd, dy / gcd);
             // (dx, dy) is now the smallest step size one can take from one of the antennas
@@ -74,14 +63,49 @@ pub fn part2(input: &str) -> String {
 
             // walk backwards from a and forwards from b until we leave the grid and mark
             // all grid positions along those directions as antinodes.
-            for ((mut x, mut y), (dx, dy)) in [((ax, ay), (-dx, -dy)), ((bx, by), (dx, dy))] {
-                while x < grid.width && y < grid.height {
-                    locations.insert((x, y));
-                    (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
-                }
+            for (start, delta) in [(a, (-dx, -dy)), (b, (dx, dy))] {
+                locations.extend(iter::successors(Some(start), |&p| grid.offset(p, delta)));
             }
         }
     }
 
-    locations.len().to_string()
+    Ok(locations.len().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "14");
+        assert_eq!(part2(&input).unwrap(), "34");
+    }
+
+    #[test]
+    fn gcd_signs() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(-12, -18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, -7), 7);
+        assert_eq!(gcd(0, 0), 0);
+    }
 }
@@ -1,3 +1,8 @@
+use crate::parse::Source;
+
+pub const NAME: &str = "Disk Fragmenter";
+pub const IMPLEMENTED: bool = true;
+
 #[derive(Debug, Clone, Copy)]
 struct Segment {
     file_id: usize,
@@ -5,8 +10,17 @@ struct Segment {
     len: usize,
 }
 
-fn parse_input(input: &str) -> Vec<Segment> {
+pub struct Input {
+    segs: Vec<Segment>,
+}
+
+pub fn parse(input: &str) -> anyhow::Result<Input> {
+    let src = Source::new(input);
     let input = input.trim();
+    if let Some(i) = input.bytes().position(|b| !b.is_ascii_digit()) {
+        return Err(src.error(&input[i..], "a digit"));
+    }
+
     let mut offset = 0;
     let mut segs = Vec::with_capacity(input.len() / 2);
 
@@ -24,7 +38,7 @@ fn parse_input(input: &str) -> Vec<Segment> {
         }
     }
 
-    segs
+    Ok(Input { segs })
 }
 
 fn compact_part1(mut segs: &[Segment]) -> Vec<Segment> {
@@ -38,11 +52,7 @@ fn compact_part1(mut segs: &[Segment]) -> Vec<Segment> {
         *tail
     };
 
-    loop {
-        let [next_seg, rest @ ..] = segs else {
-            break;
-        };
-
+    while let [next_seg, rest @ ..] = segs {
         if cur >= next_seg.start {
             compacted.push(*next_seg);
             segs = rest;
@@ -88,15 +98,20 @@ fn checksum(seg: &Segment) -> usize {
     // = seg.file_id * (seg.start * seg.len + seg.len * (seg.len - 1) / 2)
     // = seg.file_id * seg.len * (2 * seg.start + seg.len - 1) / 2
 
+    // the `- 1` would underflow for an empty file at the very start.
+    if seg.len == 0 {
+        return 0;
+    }
+
     seg.file_id * seg.len * (2 * seg.start + seg.len - 1) / 2
 }
 
-pub fn part1(input: &str) -> String {
-    compact_part1(&parse_input(input))
+pub fn part1(input: &Input) -> anyhow::Result<String> {
+    Ok(compact_part1(&input.segs)
         .iter()
         .map(checksum)
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
 fn compact_part2(segs: &[Segment]) -> Vec<Segment> {
@@ -128,10 +143,42 @@ fn compact_part2(segs: &[Segment]) -> Vec<Segment> {
     compacted
 }
 
-pub fn part2(input: &str) -> String {
-    compact_part2(&parse_input(input))
+pub fn part2(input: &Input) -> anyhow::Result<String> {
+    Ok(compact_part2(&input.segs)
         .iter()
         .map(checksum)
         .sum::<usize>()
-        .to_string()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2333133121414131402\n";
+
+    #[test]
+    fn example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&input).unwrap(), "1928");
+        assert_eq!(part2(&input).unwrap(), "2858");
+    }
+
+    #[test]
+    fn segment_checksum() {
+        let seg = |file_id, start, len| Segment { file_id, start, len };
+        assert_eq!(checksum(&seg(2, 3, 2)), 2 * 3 + 2 * 4);
+        assert_eq!(checksum(&seg(7, 0, 1)), 0);
+        assert_eq!(checksum(&seg(0, 5, 3)), 0);
+        assert_eq!(checksum(&seg(3, 0, 0)), 0);
+        assert_eq!(checksum(&seg(3, 10, 0)), 0);
+    }
+
+    #[test]
+    fn empty_files() {
+        let input = parse("0").unwrap();
+        assert_eq!(part1(&input).unwrap(), "0");
+        assert_eq!(part2(&input).unwrap(), "0");
+        assert_eq!(part1(&parse("101").unwrap()).unwrap(), "1");
+    }
 }
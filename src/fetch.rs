@@ -0,0 +1,261 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/Sp00ph/aoc2024";
+// takes precedence over the session in the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+// the contents of `options.json`. every field can be overridden on the command line
+// (or for the session, through `AOC_SESSION`), so they're all optional here.
+#[derive(Debug, Default, Deserialize)]
+pub struct Options {
+    pub year: Option<i32>,
+    pub session_id: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Options {
+    // a missing file is fine, a malformed one isn't.
+    pub fn load(path: &Path) -> anyhow::Result<Options> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("Couldn't parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Options::default()),
+            Err(e) => Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fetcher {
+    pub year: i32,
+    pub session: String,
+    pub base_url: String,
+    pub input_dir: PathBuf,
+    // how long to wait between two requests, so we don't hammer the server.
+    pub delay: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Downloaded,
+    Exists,
+    Locked,
+}
+
+// days since 1970-01-01 of the given date in the proleptic gregorian calendar.
+// see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = (if month <= 2 { year - 1 } else { year }) as i64;
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// puzzles unlock at midnight in america/new_york. december is always outside
+// of daylight saving time there, so that's a fixed UTC-5.
+pub fn unlock_time(year: i32, day: u8) -> i64 {
+    days_from_civil(year, 12, day as u32) * 86400 + 5 * 3600
+}
+
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+impl Fetcher {
+    fn path(&self, day: u8) -> PathBuf {
+        self.input_dir.join(format!("day{day}.txt"))
+    }
+
+    fn download(&self, day: u8) -> anyhow::Result<String> {
+        let url = format!(
+            "{}/{}/day/{day}/input",
+            self.base_url.trim_end_matches('/'),
+            self.year
+        );
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .context("Couldn't read the response body"),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                anyhow::bail!("GET {url} returned {code}: {}", body.trim())
+            }
+            Err(e) => Err(e).with_context(|| format!("GET {url} failed")),
+        }
+    }
+
+    // downloads the inputs for all unlocked `days` that don't exist yet. `now` is
+    // the current unix time, `report` gets called with the outcome of every day.
+    pub fn fetch(
+        &self,
+        days: impl IntoIterator<Item = u8>,
+        now: i64,
+        mut report: impl FnMut(u8, Outcome),
+    ) -> anyhow::Result<()> {
+        if days_from_civil(self.year, 1, 1) * 86400 > now {
+            anyhow::bail!("Year {} is in the future", self.year);
+        }
+        let unlocked = |day| unlock_time(self.year, day) <= now;
+
+        let mut first = true;
+        for day in days {
+            let path = self.path(day);
+            if path.exists() {
+                report(day, Outcome::Exists);
+                continue;
+            }
+            if !unlocked(day) {
+                report(day, Outcome::Locked);
+                continue;
+            }
+
+            if !first {
+                thread::sleep(self.delay);
+            }
+            first = false;
+
+            let input = self
+                .download(day)
+                .with_context(|| format!("Couldn't fetch day {day}"))?;
+            fs::create_dir_all(&self.input_dir)
+                .with_context(|| format!("Couldn't create {}", self.input_dir.display()))?;
+            fs::write(&path, input)
+                .with_context(|| format!("Couldn't write {}", path.display()))?;
+            report(day, Outcome::Downloaded);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn unlock_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(2024, 1), 1733029200);
+        assert_eq!(unlock_time(2024, 25) - unlock_time(2024, 1), 24 * 86400);
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    }
+
+    // serves every request with a body derived from the request path, after
+    // checking that it carries the session cookie and user agent.
+    fn stub_server(requests: usize) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut paths = vec![];
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut lines = BufReader::new(&stream).lines().map(Result::unwrap);
+                let path = lines.next().unwrap().split(' ').nth(1).unwrap().to_string();
+                let headers: Vec<_> = lines.take_while(|l| !l.is_empty()).collect();
+                assert!(headers.iter().any(|h| h == "Cookie: session=secret"));
+                assert!(
+                    headers
+                        .iter()
+                        .any(|h| h == &format!("User-Agent: {USER_AGENT}"))
+                );
+
+                let (status, body) = if path.contains("/day/3/") {
+                    ("404 Not Found", "no such puzzle".to_string())
+                } else {
+                    ("200 OK", format!("input for {path}\n"))
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                paths.push(path);
+            }
+            paths
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetch_from_stub() {
+        let dir = std::env::temp_dir().join(format!("aoc2024-fetch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "already there").unwrap();
+
+        let (base_url, server) = stub_server(2);
+        let fetcher = Fetcher {
+            year: 2024,
+            session: "secret".to_string(),
+            base_url,
+            input_dir: dir.clone(),
+            delay: Duration::ZERO,
+        };
+
+        let mut outcomes = vec![];
+        let now = unlock_time(2024, 3) + 60;
+        assert!(
+            fetcher
+                .fetch(1..=1, unlock_time(2023, 25), |_, _| {})
+                .is_err()
+        );
+        let res = fetcher.fetch(1..=4, now, |day, o| outcomes.push((day, o)));
+        assert!(res.unwrap_err().to_string().contains("day 3"));
+        assert_eq!(outcomes, [(1, Outcome::Exists), (2, Outcome::Downloaded)]);
+
+        assert_eq!(
+            server.join().unwrap(),
+            ["/2024/day/2/input", "/2024/day/3/input"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("day1.txt")).unwrap(),
+            "already there"
+        );
+        let day2 = fs::read_to_string(dir.join("day2.txt")).unwrap();
+        assert_eq!(day2, "input for /2024/day/2/input\n");
+        assert!(!dir.join("day3.txt").exists());
+
+        // day 4 isn't unlocked yet.
+        outcomes.clear();
+        fetcher
+            .fetch([1, 2, 4], now, |day, o| outcomes.push((day, o)))
+            .unwrap();
+        assert_eq!(
+            outcomes,
+            [
+                (1, Outcome::Exists),
+                (2, Outcome::Exists),
+                (4, Outcome::Locked)
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
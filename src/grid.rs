@@ -0,0 +1,493 @@
+use std::{
+    iter,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use bitvec::vec::BitVec;
+
+use crate::parse::Source;
+
+// positions are `(x, y)`, with `y` going down.
+pub type Pos = (usize, usize);
+
+pub trait Direction: Copy + 'static {
+    fn delta(self) -> (isize, isize);
+}
+
+// the four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    // a dense index in `0..4`, e.g. for using directions as part of a state.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Dir {
+        Dir::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir {
+        Dir::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir {
+        Dir::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir::Up | Dir::Down)
+    }
+}
+
+impl Direction for Dir {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Right => (1, 0),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+        }
+    }
+}
+
+// the four orthogonal and four diagonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+    pub const DIAGONALS: [Dir8; 4] = [Dir8::NE, Dir8::SE, Dir8::SW, Dir8::NW];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    // rotates by 45 degrees.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self.index() + 4) % 8]
+    }
+}
+
+impl Direction for Dir8 {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Dir8::N => (0, -1),
+            Dir8::NE => (1, -1),
+            Dir8::E => (1, 0),
+            Dir8::SE => (1, 1),
+            Dir8::S => (0, 1),
+            Dir8::SW => (-1, 1),
+            Dir8::W => (-1, 0),
+            Dir8::NW => (-1, -1),
+        }
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Dir8 {
+        Dir8::ALL[dir.index() * 2]
+    }
+}
+
+// how the cells of a grid are stored. this is a `Vec` for most grids, but grids
+// of flags can use a `BitVec` instead to save memory.
+pub trait Cells<T>: FromIterator<T> {
+    fn cell_count(&self) -> usize;
+    fn cell(&self, i: usize) -> T;
+    fn set_cell(&mut self, i: usize, val: T);
+}
+
+impl<T: Copy> Cells<T> for Vec<T> {
+    fn cell_count(&self) -> usize {
+        self.len()
+    }
+
+    fn cell(&self, i: usize) -> T {
+        self[i]
+    }
+
+    fn set_cell(&mut self, i: usize, val: T) {
+        self[i] = val;
+    }
+}
+
+impl Cells<bool> for BitVec {
+    fn cell_count(&self) -> usize {
+        self.len()
+    }
+
+    fn cell(&self, i: usize) -> bool {
+        self[i]
+    }
+
+    fn set_cell(&mut self, i: usize, val: bool) {
+        self.set(i, val);
+    }
+}
+
+// a dense, rectangular grid, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T, S = Vec<T>> {
+    width: usize,
+    height: usize,
+    cells: S,
+    _cell: PhantomData<T>,
+}
+
+pub type BitGrid = Grid<bool, BitVec>;
+
+impl<T: Copy, S: Cells<T>> Grid<T, S> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_cells(width, height, iter::repeat_n(fill, width * height))
+    }
+
+    // `cells` are in row-major order and have to fill the grid exactly.
+    pub fn from_cells(width: usize, height: usize, cells: impl IntoIterator<Item = T>) -> Self {
+        let cells: S = cells.into_iter().collect();
+        assert_eq!(
+            cells.cell_count(),
+            width * height,
+            "cells don't fit a {width}x{height} grid"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+            _cell: PhantomData,
+        }
+    }
+
+    // parses one row per line, turning every byte and its position into a cell.
+    // if `cell` rejects a byte with `None`, the error reports what was `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(Pos, u8) -> Option<T>,
+    ) -> anyhow::Result<Self> {
+        let src = Source::new(input);
+        let (width, mut height) = (src.lines().next().map_or(0, str::len), 0);
+        let mut cells = vec![];
+
+        for line in src.lines() {
+            if line.len() != width {
+                return Err(src.error(line, format_args!("a line of length {width}")));
+            }
+            for (x, b) in line.bytes().enumerate() {
+                match cell((x, height), b) {
+                    Some(c) => cells.push(c),
+                    None => return Err(src.error(&line[x..], expected)),
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Self::from_cells(width, height, cells))
+    }
+
+    // like `parse`, but also finds the `markers`, e.g. the start and end of a maze.
+    // every marker byte has to appear exactly once and becomes the cell it's paired
+    // with. any further occurrence is left to `cell`, which can reject it.
+    pub fn parse_with_markers<const N: usize>(
+        input: &str,
+        expected: &str,
+        markers: [(u8, T); N],
+        mut cell: impl FnMut(Pos, u8) -> Option<T>,
+    ) -> anyhow::Result<(Self, [Pos; N])> {
+        let mut found = [None; N];
+        let grid = Self::parse(input, expected, |pos, b| {
+            match markers.iter().position(|&(m, _)| m == b) {
+                Some(i) if found[i].is_none() => {
+                    found[i] = Some(pos);
+                    Some(markers[i].1)
+                }
+                _ => cell(pos, b),
+            }
+        })?;
+
+        let src = Source::new(input);
+        let mut positions = [(0, 0); N];
+        for (i, pos) in positions.iter_mut().enumerate() {
+            let marker = markers[i].0 as char;
+            *pos = found[i].ok_or_else(|| {
+                src.error(
+                    src.end_of(input),
+                    format_args!("{marker:?} somewhere in the grid"),
+                )
+            })?;
+        }
+        Ok((grid, positions))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    // the index of `pos` in row-major order. panics if it's outside of the grid,
+    // rather than silently wrapping around into the next row.
+    pub fn cell_index(&self, pos: Pos) -> usize {
+        let (width, height) = (self.width, self.height);
+        assert!(
+            self.contains(pos),
+            "{pos:?} is outside of the {width}x{height} grid"
+        );
+        pos.1 * self.width + pos.0
+    }
+
+    pub fn pos(&self, index: usize) -> Pos {
+        (index % self.width, index / self.width)
+    }
+
+    // the checked accessor, for when walking off the grid is expected.
+    pub fn get(&self, pos: Pos) -> Option<T> {
+        self.contains(pos)
+            .then(|| self.cells.cell(pos.1 * self.width + pos.0))
+    }
+
+    // the unchecked accessor, for positions that have to be on the grid. it
+    // panics if they aren't.
+    pub fn at(&self, pos: Pos) -> T {
+        self.cells.cell(self.cell_index(pos))
+    }
+
+    pub fn set(&mut self, pos: Pos, val: T) {
+        let i = self.cell_index(pos);
+        self.cells.set_cell(i, val);
+    }
+
+    // the position `delta` away from `pos`, if that's still on the grid. walking
+    // off the top or left edge wraps around to huge coordinates, which the bounds
+    // check catches just like walking off the other two edges.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn step(&self, pos: Pos, dir: impl Direction) -> Option<Pos> {
+        self.offset(pos, dir.delta())
+    }
+
+    // the up to 4 orthogonally adjacent positions.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::ALL.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    // the up to 8 orthogonally or diagonally adjacent positions.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    // all positions, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T, S> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, T)> + '_ {
+        self.positions().map(|pos| (pos, self.at(pos)))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = T> + '_ {
+        (0..self.width).map(move |x| self.at((x, y)))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = T> + '_ {
+        (0..self.height).map(move |y| self.at((x, y)))
+    }
+
+    // the cells from `pos` (inclusive) in a straight line towards `dir`, up to
+    // the edge of the grid. with a diagonal direction, this is a diagonal.
+    pub fn ray(&self, pos: Pos, dir: impl Direction) -> impl Iterator<Item = T> + '_ {
+        let first = self.contains(pos).then_some(pos);
+        iter::successors(first, move |&p| self.step(p, dir)).map(|p| self.at(p))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+}
+
+impl<T: Copy> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self.cells[self.cell_index(pos)]
+    }
+}
+
+impl<T: Copy> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let i = self.cell_index(pos);
+        &mut self.cells[i]
+    }
+}
+
+impl BitGrid {
+    pub fn count_ones(&self) -> usize {
+        self.cells.count_ones()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+abc
+def
+";
+
+    fn grid() -> Grid<u8> {
+        Grid::parse(TEXT, "a byte", |_, b| Some(b)).unwrap()
+    }
+
+    #[test]
+    fn accessors() {
+        let mut g = grid();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get((2, 1)), Some(b'f'));
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.get((0, usize::MAX)), None);
+        assert_eq!(g.at((1, 0)), b'b');
+        assert_eq!(g[(0, 1)], b'd');
+        g[(0, 1)] = b'x';
+        g.set((1, 1), b'y');
+        assert_eq!(g.row(1).collect::<Vec<_>>(), b"xyf");
+        assert_eq!(g.pos(g.cell_index((2, 1))), (2, 1));
+        assert_eq!(g.as_slice(), b"abcxyf");
+    }
+
+    #[test]
+    #[should_panic]
+    fn unchecked_out_of_bounds() {
+        // this would be `(0, 1)` if it wrapped around.
+        grid().at((3, 0));
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = grid();
+        assert_eq!(g.neighbours((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours((1, 1)).count(), 3);
+        assert_eq!(g.neighbours8((1, 0)).count(), 5);
+        assert_eq!(g.step((0, 0), Dir::Left), None);
+        assert_eq!(g.step((0, 0), Dir8::SE), Some((1, 1)));
+    }
+
+    #[test]
+    fn views() {
+        let g = grid();
+        assert_eq!(g.column(2).collect::<Vec<_>>(), b"cf");
+        assert_eq!(g.ray((0, 0), Dir8::SE).collect::<Vec<_>>(), b"ae");
+        assert_eq!(g.ray((2, 1), Dir::Left).collect::<Vec<_>>(), b"fed");
+        assert_eq!(g.ray((5, 5), Dir::Left).count(), 0);
+        assert_eq!(g.iter().nth(4), Some(((1, 1), b'e')));
+    }
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(Dir::Left.reverse(), Dir::Right);
+        assert!(Dir::ALL.iter().all(|d| d.turn_right().turn_left() == *d));
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::NE.reverse(), Dir8::SW);
+        for d in Dir::ALL {
+            assert_eq!(Dir8::from(d).delta(), d.delta());
+        }
+    }
+
+    #[test]
+    fn bit_grid() {
+        let mut g = BitGrid::parse(TEXT, "a byte", |_, b| Some(b > b'c')).unwrap();
+        assert_eq!(g.count_ones(), 3);
+        g.set((0, 0), true);
+        assert_eq!(g.get((0, 0)), Some(true));
+        assert_eq!(g.count_ones(), 4);
+        assert_eq!(BitGrid::new(4, 4, false).count_ones(), 0);
+    }
+
+    #[test]
+    fn markers() {
+        let floor = |_, b| (b == b'.' || b == b'#').then_some(b == b'#');
+        let parse = |text| {
+            BitGrid::parse_with_markers(text, "a tile", [(b'S', false), (b'E', false)], floor)
+        };
+
+        let (grid, [start, end]) = parse("S.#\n#.E\n").unwrap();
+        assert_eq!((start, end), ((0, 0), (2, 1)));
+        assert_eq!(grid.count_ones(), 2);
+
+        let err = parse("S.#\n#.S\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected a tile, found \"S\""
+        );
+        let err = parse("S.#\n#..\n").err().unwrap();
+        let expected = "line 3, column 1: expected 'E' somewhere in the grid, found end of input";
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Grid::<u8>::parse("ab\nabc\n", "a byte", |_, b| Some(b))
+            .err()
+            .unwrap();
+        let expected = "line 2, column 1: expected a line of length 2, found \"abc\"";
+        assert_eq!(err.to_string(), expected);
+
+        let letter = |_, b: u8| b.is_ascii_alphabetic().then_some(b);
+        let err = Grid::<u8>::parse("ab\na!\n", "a letter", letter)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected a letter, found \"!\""
+        );
+    }
+}
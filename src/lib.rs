@@ -0,0 +1,120 @@
+use seq_macro::seq;
+
+pub mod baseline;
+pub mod bench;
+pub mod fetch;
+pub mod grid;
+pub mod parse;
+
+seq!(N in 1..=25 {
+    #(pub mod day~N;)*
+});
+
+use std::any::Any;
+
+use anyhow::Context;
+
+// the parsed input of a day, shared by both of its parts. the concrete type is
+// the day's `Input`, which solvers downcast to again.
+pub type Parsed = Box<dyn Any>;
+
+pub type ParseFn = fn(&str) -> anyhow::Result<Parsed>;
+pub type PartFn = fn(&dyn Any) -> anyhow::Result<String>;
+
+// a single part of a single day. days that are still `todo!()` stubs are registered
+// too, but with `implemented` unset, so callers can skip them instead of panicking.
+#[derive(Clone, Copy, Debug)]
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    // both parts of a day share the same parse function, so the result of parsing
+    // with one of them can be passed to the other one's `solve` too.
+    pub parse: ParseFn,
+    pub func: PartFn,
+    pub implemented: bool,
+}
+
+impl Solver {
+    pub fn parse(&self, input: &str) -> anyhow::Result<Parsed> {
+        (self.parse)(input)
+    }
+
+    pub fn solve(&self, parsed: &dyn Any) -> anyhow::Result<String> {
+        (self.func)(parsed)
+    }
+
+    pub fn run(&self, input: &str) -> anyhow::Result<String> {
+        self.solve(&*self.parse(input)?)
+    }
+}
+
+fn erase<T: 'static>(input: anyhow::Result<T>) -> anyhow::Result<Parsed> {
+    Ok(Box::new(input?))
+}
+
+fn downcast<T: 'static>(parsed: &dyn Any) -> anyhow::Result<&T> {
+    parsed
+        .downcast_ref()
+        .context("Input was parsed by a different day's solver")
+}
+
+seq!(N in 1..=25 {
+    // all solvers, ordered by day and then by part.
+    pub static SOLVERS: [Solver; 50] = [
+        #(
+            Solver {
+                day: N,
+                part: 1,
+                name: day~N::NAME,
+                parse: |input| erase(day~N::parse(input)),
+                func: |parsed| day~N::part1(downcast(parsed)?),
+                implemented: day~N::IMPLEMENTED,
+            },
+            Solver {
+                day: N,
+                part: 2,
+                name: day~N::NAME,
+                parse: |input| erase(day~N::parse(input)),
+                func: |parsed| day~N::part2(downcast(parsed)?),
+                implemented: day~N::IMPLEMENTED,
+            },
+        )*
+    ];
+});
+
+pub fn solver(day: u8, part: u8) -> Option<&'static Solver> {
+    if !(1..=25).contains(&day) || !(1..=2).contains(&part) {
+        return None;
+    }
+    Some(&SOLVERS[(day as usize - 1) * 2 + (part as usize - 1)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_parse() {
+        let parsed = solver(11, 1).unwrap().parse("125 17").unwrap();
+        assert_eq!(solver(11, 1).unwrap().solve(&*parsed).unwrap(), "55312");
+        assert_eq!(
+            solver(11, 2).unwrap().solve(&*parsed).unwrap(),
+            "65601038650482"
+        );
+        assert!(solver(10, 1).unwrap().solve(&*parsed).is_err());
+    }
+
+    #[test]
+    fn registry_order() {
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let s = solver(day, part).unwrap();
+                assert_eq!((s.day, s.part), (day, part));
+            }
+        }
+        assert!(solver(0, 1).is_none());
+        assert!(solver(26, 1).is_none());
+        assert!(solver(1, 3).is_none());
+    }
+}
@@ -1,32 +1,34 @@
 use std::{
+    any::Any,
     hint::black_box,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::ExitCode,
     time::{Duration, Instant},
 };
 
 use anyhow::Context;
+use aoc2024::{
+    Solver,
+    baseline::{self, Baseline, Machine, Phase, Timing},
+    bench, fetch,
+};
 use clap::{Parser, value_parser};
-use seq_macro::seq;
-
-type PartFn = fn(&str) -> String;
-
-seq!(N in 1..=25 {
-    #(mod day~N;)*
+use report::{Format, Record, Reporter, Status};
 
-    #[used]
-    static FNS: [[PartFn; 2]; 25] = [
-        #(
-            [day~N::part1, day~N::part2],
-        )*
-    ];
-});
+mod answers;
+mod report;
 
 #[derive(Parser)]
 #[allow(clippy::enum_variant_names)]
 enum Args {
     #[clap(alias = "rp")]
     RunPart {
-        day: usize,
-        part: usize,
+        #[arg(value_parser = value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(value_parser = value_parser!(u8).range(1..=2))]
+        part: u8,
         #[arg(short, long)]
         input: Option<String>,
         #[arg(short = 't', long)]
@@ -38,10 +40,15 @@ enum Args {
             value_parser = value_parser!(u32).range(1..)
         )]
         iterations: u32,
+        /// How to print the results. `json` and `csv` print one record per part to
+        /// stdout, with everything else going to stderr.
+        #[arg(short = 'f', long, value_enum, default_value_t = Format::Text)]
+        format: Format,
     },
     #[clap(alias = "rd")]
     RunDay {
-        day: usize,
+        #[arg(value_parser = value_parser!(u8).range(1..=25))]
+        day: u8,
         #[clap(short, long)]
         input: Option<String>,
         #[arg(short = 't', long)]
@@ -56,6 +63,10 @@ enum Args {
             value_parser = value_parser!(u32).range(1..)
         )]
         iterations: u32,
+        /// How to print the results. `json` and `csv` print one record per part to
+        /// stdout, with everything else going to stderr.
+        #[arg(short = 'f', long, value_enum, default_value_t = Format::Text)]
+        format: Format,
     },
     #[clap(alias = "ra")]
     RunAll {
@@ -71,45 +82,448 @@ enum Args {
             value_parser = value_parser!(u32).range(1..)
         )]
         iterations: u32,
+        /// Save the timings as a named baseline in `baselines/<NAME>.json`.
+        #[arg(long, value_name = "NAME", requires = "show_time")]
+        save_baseline: Option<String>,
+        /// Compare the timings against a previously saved baseline.
+        #[arg(long, value_name = "NAME", requires = "show_time")]
+        compare: Option<String>,
+        /// Fail if any part got significantly slower than the baseline by more than this many
+        /// percent.
+        #[arg(long, default_value_t = 5.0, requires = "compare")]
+        threshold: f64,
+        /// How to print the results. `json` and `csv` print one record per part to
+        /// stdout, with everything else going to stderr.
+        #[arg(short = 'f', long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Benchmark one or both parts of a day with per-iteration statistics.
+    #[clap(alias = "b")]
+    Bench {
+        #[arg(value_parser = value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Only benchmark this part.
+        #[arg(value_parser = value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Iterations to run before taking any samples.
+        #[arg(short, long, default_value_t = 3)]
+        warmup: u32,
+        /// Roughly how long to spend taking samples, e.g. `500ms` or `2s`.
+        #[arg(short = 'T', long, default_value = "1s", value_parser = parse_duration)]
+        target_time: Duration,
+        /// Take exactly this many samples instead of deriving it from the target time.
+        #[arg(short = 'n', long, value_parser = value_parser!(u32).range(1..))]
+        samples: Option<u32>,
+    },
+    /// Download all missing puzzle inputs into `input/`.
+    Fetch {
+        /// Only fetch this day.
+        #[arg(value_parser = value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// JSON file with the `year`, `session_id` and optionally `base_url` to use.
+        /// The session can also be set through the `AOC_SESSION` environment variable.
+        #[arg(long, default_value = "options.json")]
+        config: PathBuf,
+        #[arg(long)]
+        year: Option<i32>,
+        /// Where to download the inputs from, e.g. a local test server.
+        #[arg(long)]
+        base_url: Option<String>,
+        /// How long to wait between two downloads.
+        #[arg(long, default_value = "1s", value_parser = parse_duration)]
+        delay: Duration,
+    },
+    /// Check every part's answer against the ones recorded in `answers/`.
+    #[clap(alias = "v")]
+    Verify {
+        /// Only verify this day.
+        #[arg(value_parser = value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Record the current answers as the new known-correct ones instead.
+        #[arg(long)]
+        record: bool,
     },
 }
 
-fn run_part(
-    day: usize,
-    part: usize,
-    input: Option<String>,
-    show_time: bool,
-    acc: Option<&mut Duration>,
-    iterations: u32,
-) -> anyhow::Result<()> {
-    let input = match input {
-        Some(input) => input,
-        None => std::fs::read_to_string(format!("input/day{}.txt", day))
-            .context("Input for this day isn't available.")?,
+fn input_path(day: u8) -> String {
+    format!("input/day{}.txt", day)
+}
+
+fn read_input(day: u8) -> anyhow::Result<String> {
+    std::fs::read_to_string(input_path(day)).context("Input for this day isn't available.")
+}
+
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    // `µ` isn't ascii, so the unit starts at the first character that can't be
+    // part of the number.
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid number {value:?}"))?;
+    let scale = match unit {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" | "" => 1.0,
+        _ => {
+            return Err(format!(
+                "unknown unit {unit:?}, expected one of ns, us, ms, s"
+            ));
+        }
     };
-    let f = &FNS[day - 1][part - 1];
+    Duration::try_from_secs_f64(value * scale).map_err(|e| e.to_string())
+}
+
+fn bench_day(
+    day: u8,
+    parts: RangeInclusive<u8>,
+    input: &str,
+    config: &bench::Config,
+) -> anyhow::Result<()> {
+    let parser = get_solver(day, 1)?;
+    if !parser.implemented {
+        anyhow::bail!("Day {day} isn't implemented yet");
+    }
+    // make sure everything actually works before spending any time on measuring it.
+    let parsed = parser
+        .parse(input)
+        .with_context(|| format!("Day {day} Parsing failed"))?;
+    let stats = bench::run(config, || parser.parse(input));
+    println!("===== Day {} Parsing =====", day);
+    println!("{stats}");
+
+    for part in parts {
+        let solver = get_solver(day, part)?;
+        let output = solver
+            .solve(&*parsed)
+            .with_context(|| format!("Day {day} Part {part} failed"))?;
+        let stats = bench::run(config, || solver.solve(&*parsed));
+
+        println!("===== Day {} Part {} =====", day, part);
+        println!("{}", output);
+        println!("{stats}");
+    }
+    Ok(())
+}
+
+fn get_solver(day: u8, part: u8) -> anyhow::Result<&'static Solver> {
+    aoc2024::solver(day, part).with_context(|| format!("There is no Day {day} Part {part}"))
+}
+
+// runs `f` `iterations` times, timing each iteration separately. only the result
+// of the first iteration is kept, and the others only run if that one succeeded.
+fn timed<T>(
+    iterations: u32,
+    mut f: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<(T, bench::Stats)> {
+    let mut samples = Vec::with_capacity(iterations as usize);
     let now = Instant::now();
-    let output = f(&input);
+    let first = f()?;
+    samples.push(now.elapsed());
     for _ in 1..iterations {
-        black_box(f(&input));
+        let now = Instant::now();
+        // dropping the result isn't part of the measurement.
+        let res = black_box(f());
+        samples.push(now.elapsed());
+        drop(res);
     }
-    let elapsed = now.elapsed();
-    println!("===== Day {} Part {} =====", day, part);
-    println!("{}", output);
-    if show_time {
-        println!(
-            "Average runtime across {iterations} iterations: {:.3?}",
-            elapsed / iterations
-        );
+    Ok((first, bench::Stats::from_samples(&samples)))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "Box<dyn Any>"
     }
-    if let Some(acc) = acc {
-        *acc += elapsed;
+}
+
+// runs `f`, turning both errors and panics into a failing `Status`.
+fn isolate<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Result<T, Status> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(t)) => Ok(t),
+        Ok(Err(e)) => Err(Status::Error(format!("{e:#}"))),
+        Err(payload) => Err(Status::Panicked(panic_message(&*payload).to_string())),
+    }
+}
+
+// the default hook would print every panic message (and possibly a backtrace)
+// in the middle of the output, so it's disabled while running `f`. the messages
+// end up in the `Status`es returned by `isolate` instead. a panic that gets past
+// `isolate` anyway is only resumed once the hook is back, since the hook can't be
+// changed while unwinding and every later panic would be silent otherwise.
+fn with_silent_panics<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    res.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+// runs `parts` of `day` on `input`, or on `input/dayN.txt` if that's `None`. nothing
+// is printed here, and any failure ends up as the status of the affected parts. if
+// reading or parsing the input fails, that's the status of all of them.
+fn run_day(
+    day: u8,
+    parts: RangeInclusive<u8>,
+    input: Option<String>,
+    iterations: u32,
+) -> Vec<Record> {
+    let parser = &aoc2024::SOLVERS[(day as usize - 1) * 2];
+    let mut records: Vec<_> = parts
+        .map(|part| Record::new(day, part, parser.name, iterations))
+        .collect();
+    let fail_all = |mut records: Vec<Record>, status: Status| {
+        for r in &mut records {
+            r.set_status(status.clone());
+        }
+        records
+    };
+
+    if !parser.implemented {
+        return fail_all(records, Status::NotImplemented);
+    }
+    let (input, path) = match input {
+        Some(input) => (input, None),
+        None => match read_input(day) {
+            Ok(input) => (input, Some(input_path(day))),
+            Err(_) => return fail_all(records, Status::NoInput),
+        },
+    };
+    let hash = report::hash_input(&input);
+    for r in &mut records {
+        r.input_path = path.clone();
+        r.input_hash = Some(hash.clone());
+    }
+
+    let parsed = isolate(|| {
+        timed(iterations, || parser.parse(&input))
+            .with_context(|| format!("Day {day} Parsing failed"))
+    });
+    let parsed = match parsed {
+        Ok((parsed, stats)) => {
+            for r in &mut records {
+                r.parse = Some(stats.clone());
+            }
+            parsed
+        }
+        Err(status) => return fail_all(records, status),
+    };
+
+    for r in &mut records {
+        let part = r.part;
+        let res = isolate(|| {
+            let solver = get_solver(day, part)?;
+            timed(iterations, || solver.solve(&*parsed))
+                .with_context(|| format!("Day {day} Part {part} failed"))
+        });
+        match res {
+            Ok((answer, stats)) => {
+                r.answer = Some(answer);
+                r.solve = Some(stats);
+            }
+            Err(status) => r.set_status(status),
+        }
+    }
+    records
+}
+
+// `run-part` and `run-day` stop at the first part that didn't succeed.
+fn check(records: &[Record]) -> anyhow::Result<()> {
+    for r in records {
+        match &r.status {
+            Status::Ok => {}
+            Status::NotImplemented => anyhow::bail!("Day {} isn't implemented yet", r.day),
+            Status::NoInput => anyhow::bail!("Input for this day isn't available."),
+            Status::Error(msg) => anyhow::bail!("{msg}"),
+            Status::Panicked(msg) => anyhow::bail!("Day {} Part {} panicked: {msg}", r.day, r.part),
+        }
     }
     Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
+// the sum of the mean parse and solve times of `records`, i.e. how long running
+// all of them once takes on average.
+fn total_time(records: &[Record]) -> Duration {
+    let mut total = Duration::ZERO;
+    let mut days = vec![];
+    for r in records {
+        // the parse timing is shared by both parts of a day, so only count it once.
+        if let Some(parse) = &r.parse
+            && !days.contains(&r.day)
+        {
+            days.push(r.day);
+            total += parse.mean;
+        }
+        if let Some(solve) = &r.solve {
+            total += solve.mean;
+        }
+    }
+    total
+}
+
+// the timings of every phase that succeeded, for saving or comparing baselines.
+fn timings(records: &[Record]) -> Vec<Timing> {
+    let mut timings = vec![];
+    for r in records {
+        if let Some(parse) = &r.parse
+            && !timings
+                .iter()
+                .any(|t: &Timing| (t.day, t.phase) == (r.day, Phase::Parse))
+        {
+            timings.push(Timing::new(r.day, Phase::Parse, parse));
+        }
+        if let Some(solve) = &r.solve {
+            timings.push(Timing::new(r.day, Phase::part(r.part), solve));
+        }
+    }
+    timings
+}
+
+// prints how every phase compares to the baseline and returns how many of them
+// regressed by more than `threshold` percent.
+fn compare_baseline(
+    baseline: &Baseline,
+    timings: &[Timing],
+    threshold: f64,
+    reporter: &Reporter,
+) -> usize {
+    reporter.note(format_args!(
+        "===== Comparison with {} =====",
+        baseline.name
+    ));
+    if baseline.machine != Machine::current() {
+        reporter.note("note: the baseline was recorded on a different machine");
+    }
+    if baseline.iterations < 2 || timings.iter().any(|t| t.stats.samples < 2) {
+        reporter.note("note: a single iteration can't tell changes from noise, use -n 2 or more");
+    }
+
+    let mut regressions = 0;
+    for cmp in baseline::compare(baseline, timings) {
+        let change = cmp.change() * 100.0;
+        let verdict = if !cmp.significant() {
+            "no change"
+        } else if change < 0.0 {
+            "faster"
+        } else {
+            "slower"
+        };
+        let regressed = cmp.is_regression(threshold / 100.0);
+        regressions += regressed as usize;
+
+        reporter.note(format_args!(
+            "Day {:>2} {:<8} {:>10.3?} -> {:>10.3?} {change:+7.1}% {:<3} {verdict}{}",
+            cmp.day,
+            format!("{}:", cmp.phase),
+            Duration::from_secs_f64(cmp.old_ns / 1e9),
+            Duration::from_secs_f64(cmp.new_ns / 1e9),
+            cmp.marker(),
+            if regressed { " REGRESSION" } else { "" },
+        ));
+    }
+    regressions
+}
+
+// how many parts `verify` checked, and how that went.
+#[derive(Default)]
+struct Tally {
+    // passed, or recorded with `--record`.
+    passed: usize,
+    failed: usize,
+    // solved, but there's no recorded answer to compare with.
+    missing: usize,
+    // not implemented or without an input, so there's no answer to check.
+    skipped: usize,
+    // the subset of `skipped` that does have a recorded answer.
+    unchecked: usize,
+}
+
+impl Tally {
+    fn add(&mut self, other: &Tally) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.missing += other.missing;
+        self.skipped += other.skipped;
+        self.unchecked += other.unchecked;
+    }
+}
+
+// verifies (or records) the answers for a single day.
+fn verify_day(day: u8, record: bool) -> anyhow::Result<Tally> {
+    let expected = answers::load(day)?;
+    let mut recorded = [None, None];
+    let mut tally = Tally::default();
+
+    for r in run_day(day, 1..=2, None, 1) {
+        let i = r.part as usize - 1;
+        let actual = match (r.status, r.answer) {
+            (Status::Ok, Some(answer)) => Ok(answer),
+            (status, _) => Err(status),
+        };
+
+        print!("Day {day:>2} Part {}: ", r.part);
+        match (actual, &expected[i]) {
+            (Err(status), _) if status.is_failure() => {
+                println!("{status}");
+                tally.failed += 1;
+            }
+            (Err(status), Some(_)) if !record => {
+                println!("{status}, so the recorded answer can't be checked");
+                tally.skipped += 1;
+                tally.unchecked += 1;
+            }
+            (Err(status), _) => {
+                println!("{status}");
+                tally.skipped += 1;
+            }
+            (Ok(actual), _) if record => {
+                println!("recorded {actual}");
+                recorded[i] = Some(actual);
+                tally.passed += 1;
+            }
+            (Ok(actual), None) => {
+                println!("missing (got {actual})");
+                tally.missing += 1;
+            }
+            (Ok(actual), Some(exp)) if actual == *exp => {
+                println!("pass");
+                tally.passed += 1;
+            }
+            (Ok(actual), Some(exp)) => {
+                println!("FAIL");
+                println!("  expected: {exp}");
+                println!("  actual:   {actual}");
+                tally.failed += 1;
+            }
+        }
+    }
+
+    if record {
+        answers::record(day, recorded)?;
+    }
+    Ok(tally)
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // print the whole context chain on one line, without a backtrace.
+            eprintln!("error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
     match args {
         Args::RunPart {
             day,
@@ -117,24 +531,33 @@ fn main() -> anyhow::Result<()> {
             input,
             show_time,
             iterations,
-        } => run_part(day, part, input, show_time, None, iterations),
+            format,
+        } => {
+            let mut reporter = Reporter::new(format, show_time);
+            let records = with_silent_panics(|| run_day(day, part..=part, input, iterations));
+            reporter.day(&records);
+            reporter.finish()?;
+            check(&records)
+        }
         Args::RunDay {
             day,
             input,
             show_time,
             show_total_time,
             iterations,
+            format,
         } => {
-            let mut acc = show_total_time.then_some(Duration::ZERO);
-
-            run_part(day, 1, input.clone(), show_time, acc.as_mut(), iterations)?;
-            run_part(day, 2, input, show_time, acc.as_mut(), iterations)?;
+            let mut reporter = Reporter::new(format, show_time);
+            let records = with_silent_panics(|| run_day(day, 1..=2, input, iterations));
+            reporter.day(&records);
+            reporter.finish()?;
+            check(&records)?;
 
-            if let Some(acc) = acc {
-                println!(
+            if show_total_time {
+                reporter.note(format_args!(
                     "Average total time across {iterations} iterations: {:.3?}",
-                    acc / iterations
-                );
+                    total_time(&records)
+                ));
             }
             Ok(())
         }
@@ -142,20 +565,158 @@ fn main() -> anyhow::Result<()> {
             show_time,
             show_total_time,
             iterations,
+            save_baseline,
+            compare,
+            threshold,
+            format,
         } => {
-            let mut acc = show_total_time.then_some(Duration::ZERO);
-            for day in 1..=25 {
-                run_part(day, 1, None, show_time, acc.as_mut(), iterations)?;
-                run_part(day, 2, None, show_time, acc.as_mut(), iterations)?;
-            }
+            // load the baseline first so a typo in its name doesn't waste a whole run.
+            let baseline = compare.as_deref().map(Baseline::load).transpose()?;
+            let mut reporter = Reporter::new(format, show_time);
+            let mut records = vec![];
+
+            with_silent_panics(|| {
+                for day in 1..=25 {
+                    let day = run_day(day, 1..=2, None, iterations);
+                    reporter.day(&day);
+                    records.extend(day);
+                }
+            });
+            reporter.finish()?;
 
-            if let Some(acc) = acc {
-                println!(
+            if show_total_time {
+                reporter.note(format_args!(
                     "Average time across {iterations} iterations: {:.3?}",
-                    acc / iterations
-                );
+                    total_time(&records)
+                ));
+            }
+
+            reporter.note("===== Summary =====");
+            for r in &records {
+                reporter.note(format_args!(
+                    "Day {:>2} Part {}: {}",
+                    r.day, r.part, r.status
+                ));
+            }
+
+            let timings = timings(&records);
+            if let Some(name) = save_baseline {
+                let path = Baseline::new(&name, iterations, timings.clone()).save()?;
+                reporter.note(format_args!("Saved baseline to {}", path.display()));
+            }
+            let regressions = baseline.map_or(0, |baseline| {
+                compare_baseline(&baseline, &timings, threshold, &reporter)
+            });
+
+            let failures = records.iter().filter(|r| r.status.is_failure()).count();
+            if failures > 0 {
+                anyhow::bail!("{failures} of {} parts failed", records.len());
+            }
+            if regressions > 0 {
+                anyhow::bail!("{regressions} phase(s) regressed by more than {threshold}%");
+            }
+            Ok(())
+        }
+        Args::Bench {
+            day,
+            part,
+            input,
+            warmup,
+            target_time,
+            samples,
+        } => {
+            let input = match input {
+                Some(input) => input,
+                None => read_input(day)?,
+            };
+            let config = bench::Config {
+                warmup,
+                target_time,
+                samples,
+                ..Default::default()
+            };
+            bench_day(day, part.map_or(1..=2, |part| part..=part), &input, &config)
+        }
+        Args::Fetch {
+            day,
+            config,
+            year,
+            base_url,
+            delay,
+        } => {
+            let options = fetch::Options::load(&config)?;
+            let session = std::env::var(fetch::SESSION_VAR)
+                .ok()
+                .or(options.session_id);
+            let fetcher = fetch::Fetcher {
+                year: year.or(options.year).unwrap_or(2024),
+                session: session.with_context(|| {
+                    format!(
+                        "No session found in {} or ${}",
+                        config.display(),
+                        fetch::SESSION_VAR
+                    )
+                })?,
+                base_url: base_url
+                    .or(options.base_url)
+                    .unwrap_or_else(|| fetch::DEFAULT_BASE_URL.to_string()),
+                input_dir: PathBuf::from("input"),
+                delay,
+            };
+
+            let days = day.map_or(1..=25, |day| day..=day);
+            fetcher.fetch(days, fetch::now(), |day, outcome| match outcome {
+                fetch::Outcome::Downloaded => println!("Day {day:>2}: downloaded"),
+                fetch::Outcome::Exists => println!("Day {day:>2}: already exists"),
+                fetch::Outcome::Locked => println!("Day {day:>2}: not unlocked yet"),
+            })
+        }
+        Args::Verify { day, record } => {
+            let days = day.map_or(1..=25, |day| day..=day);
+            let mut totals = Tally::default();
+            with_silent_panics(|| {
+                for day in days {
+                    totals.add(&verify_day(day, record)?);
+                }
+                anyhow::Ok(())
+            })?;
+
+            let Tally {
+                passed,
+                failed,
+                missing,
+                skipped,
+                unchecked,
+            } = totals;
+            if record {
+                println!("{passed} recorded, {failed} failed, {skipped} skipped");
+            } else {
+                println!("{passed} passed, {failed} failed, {missing} missing, {skipped} skipped");
+            }
+            if failed > 0 {
+                anyhow::bail!("{failed} part(s) failed verification");
+            }
+            if unchecked > 0 {
+                anyhow::bail!("{unchecked} recorded answer(s) couldn't be checked");
             }
             Ok(())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("250ns"), Ok(Duration::from_nanos(250)));
+        assert_eq!(parse_duration("5us"), Ok(Duration::from_micros(5)));
+        assert_eq!(parse_duration("5µs"), Ok(Duration::from_micros(5)));
+        assert_eq!(parse_duration("1.5ms"), Ok(Duration::from_micros(1500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("3"), Ok(Duration::from_secs(3)));
+        assert!(parse_duration("5m").is_err());
+        assert!(parse_duration("ms").is_err());
+    }
+}
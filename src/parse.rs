@@ -0,0 +1,123 @@
+use std::{fmt, str::FromStr};
+
+// a parse error pointing at a specific location in the puzzle input.
+// lines and columns are 1-based, like every editor displays them.
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    col: usize,
+    expected: String,
+    found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.col, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// a thin wrapper around the puzzle input that turns subslices of it back into
+// line/column positions. all `&str` arguments of its methods must point into
+// the original input (an empty slice at the end of a line is fine), since the
+// position is recovered from the pointer offset.
+#[derive(Clone, Copy)]
+pub struct Source<'a> {
+    text: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+
+    fn offset(&self, at: &str) -> usize {
+        (at.as_ptr() as usize)
+            .wrapping_sub(self.text.as_ptr() as usize)
+            .min(self.text.len())
+    }
+
+    pub fn error(&self, at: &str, expected: impl fmt::Display) -> anyhow::Error {
+        let offset = self.offset(at);
+        let before = &self.text.as_bytes()[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let col = offset - line_start + 1;
+
+        // an empty `at` still tells us whether we ran into the end of a line.
+        let at = if at.is_empty() {
+            self.text.get(offset..).unwrap_or("")
+        } else {
+            at
+        };
+        let found = match at.chars().next() {
+            None => "end of input".to_string(),
+            Some('\n' | '\r') => "end of line".to_string(),
+            Some(c) if c.is_ascii_whitespace() => format!("{c:?}"),
+            Some(_) => {
+                // only show the offending token, not the rest of the line.
+                let token = at.split_ascii_whitespace().next().unwrap_or(at);
+                let short: String = token.chars().take(16).collect();
+                if short.len() < token.len() {
+                    format!("{short:?}...")
+                } else {
+                    format!("{short:?}")
+                }
+            }
+        };
+
+        ParseError {
+            line,
+            col,
+            expected: expected.to_string(),
+            found,
+        }
+        .into()
+    }
+
+    // the empty slice right after `s`, for reporting missing tokens.
+    pub fn end_of(&self, s: &'a str) -> &'a str {
+        &s[s.len()..]
+    }
+
+    pub fn num<T: FromStr>(&self, s: &'a str) -> anyhow::Result<T> {
+        s.parse().map_err(|_| self.error(s, "a number"))
+    }
+
+    pub fn split_once(&self, s: &'a str, delim: &str) -> anyhow::Result<(&'a str, &'a str)> {
+        s.split_once(delim)
+            .ok_or_else(|| self.error(self.end_of(s), format_args!("{delim:?}")))
+    }
+
+    // unwraps the next item of an iterator over subslices of `line`, reporting
+    // a missing `expected` at the end of the line otherwise.
+    pub fn next(
+        &self,
+        iter: &mut impl Iterator<Item = &'a str>,
+        line: &'a str,
+        expected: impl fmt::Display,
+    ) -> anyhow::Result<&'a str> {
+        iter.next()
+            .ok_or_else(|| self.error(self.end_of(line), expected))
+    }
+
+    pub fn next_num<T: FromStr>(
+        &self,
+        iter: &mut impl Iterator<Item = &'a str>,
+        line: &'a str,
+    ) -> anyhow::Result<T> {
+        self.num(self.next(iter, line, "a number")?)
+    }
+}
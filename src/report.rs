@@ -0,0 +1,295 @@
+use std::fmt;
+
+use aoc2024::bench::{Stats, StatsNs};
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+// the outcome of a single part. `run-all` keeps going past failing parts.
+#[derive(Clone, Debug)]
+pub enum Status {
+    Ok,
+    NotImplemented,
+    NoInput,
+    Error(String),
+    Panicked(String),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Error(_) | Status::Panicked(_))
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::NotImplemented => "not implemented",
+            Status::NoInput => "no input",
+            Status::Error(_) => "error",
+            Status::Panicked(_) => "panicked",
+        }
+    }
+
+    fn message(&self) -> Option<&str> {
+        match self {
+            Status::Error(msg) | Status::Panicked(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message() {
+            Some(msg) => write!(f, "{}: {msg}", self.kind()),
+            None => f.write_str(self.kind()),
+        }
+    }
+}
+
+impl Serialize for Status {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.kind())
+    }
+}
+
+const CSV_STATS_FIELDS: [&str; 7] = [
+    "samples",
+    "min_ns",
+    "median_ns",
+    "mean_ns",
+    "p95_ns",
+    "max_ns",
+    "stddev_ns",
+];
+
+fn csv_stats_values(stats: Option<&Stats>) -> [String; 7] {
+    match stats.map(StatsNs::from) {
+        Some(t) => [
+            t.samples.to_string(),
+            t.min_ns.to_string(),
+            t.median_ns.to_string(),
+            t.mean_ns.to_string(),
+            t.p95_ns.to_string(),
+            t.max_ns.to_string(),
+            t.stddev_ns.to_string(),
+        ],
+        None => Default::default(),
+    }
+}
+
+// `Stats` are serialized the same way as in baselines, with all times in nanoseconds.
+fn serialize_stats<S: Serializer>(stats: &Option<Stats>, s: S) -> Result<S::Ok, S::Error> {
+    stats.as_ref().map(StatsNs::from).serialize(s)
+}
+
+// everything we know about running a single part. the parse timing is shared
+// between both parts of a day, so it shows up in both of their records.
+#[derive(Clone, Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub status: Status,
+    pub message: Option<String>,
+    pub answer: Option<String>,
+    pub iterations: u32,
+    // `None` if the input was passed on the command line.
+    pub input_path: Option<String>,
+    pub input_hash: Option<String>,
+    #[serde(serialize_with = "serialize_stats")]
+    pub parse: Option<Stats>,
+    #[serde(serialize_with = "serialize_stats")]
+    pub solve: Option<Stats>,
+}
+
+impl Record {
+    pub fn new(day: u8, part: u8, name: &'static str, iterations: u32) -> Record {
+        Record {
+            day,
+            part,
+            name,
+            status: Status::Ok,
+            message: None,
+            answer: None,
+            iterations,
+            input_path: None,
+            input_hash: None,
+            parse: None,
+            solve: None,
+        }
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.message = status.message().map(str::to_string);
+        self.status = status;
+    }
+}
+
+// 64 bit FNV-1a. it only needs to tell inputs apart, and unlike the std hashers
+// it's guaranteed to be stable across runs and compiler versions.
+pub fn hash_input(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub struct Reporter {
+    format: Format,
+    show_time: bool,
+    records: Vec<Record>,
+    csv_header_written: bool,
+}
+
+impl Reporter {
+    pub fn new(format: Format, show_time: bool) -> Reporter {
+        Reporter {
+            format,
+            show_time,
+            records: vec![],
+            csv_header_written: false,
+        }
+    }
+
+    // free-form text like summaries or totals. it goes to stderr for the machine
+    // readable formats, so stdout stays parseable.
+    pub fn note(&self, msg: impl fmt::Display) {
+        match self.format {
+            Format::Text => println!("{msg}"),
+            Format::Json | Format::Csv => eprintln!("{msg}"),
+        }
+    }
+
+    // reports all the parts of a single day.
+    pub fn day(&mut self, records: &[Record]) {
+        match self.format {
+            Format::Text => self.text(records),
+            Format::Json => self.records.extend_from_slice(records),
+            Format::Csv => self.csv(records),
+        }
+    }
+
+    fn text(&self, records: &[Record]) {
+        if self.show_time
+            && let Some(parse) = records.iter().find_map(|r| r.parse.as_ref())
+        {
+            println!("===== Day {} Parsing =====", records[0].day);
+            println!(
+                "Average parse time across {} iterations: {:.3?}",
+                records[0].iterations, parse.mean
+            );
+        }
+
+        // failures aren't printed here. the callers either summarize or bail on them.
+        for r in records {
+            let (Status::Ok, Some(answer)) = (&r.status, &r.answer) else {
+                continue;
+            };
+            println!("===== Day {} Part {} =====", r.day, r.part);
+            println!("{}", answer);
+            if let (true, Some(solve)) = (self.show_time, &r.solve) {
+                println!(
+                    "Average solve time across {} iterations: {:.3?}",
+                    r.iterations, solve.mean
+                );
+            }
+        }
+    }
+
+    fn csv(&mut self, records: &[Record]) {
+        if !self.csv_header_written {
+            let mut header: Vec<String> = [
+                "day",
+                "part",
+                "name",
+                "status",
+                "message",
+                "answer",
+                "iterations",
+                "input_path",
+                "input_hash",
+            ]
+            .map(str::to_string)
+            .into();
+            for phase in ["parse", "solve"] {
+                header.extend(CSV_STATS_FIELDS.map(|f| format!("{phase}_{f}")));
+            }
+            println!("{}", header.join(","));
+            self.csv_header_written = true;
+        }
+
+        for r in records {
+            let mut row = vec![
+                r.day.to_string(),
+                r.part.to_string(),
+                csv_field(r.name),
+                csv_field(r.status.kind()),
+                csv_field(r.message.as_deref().unwrap_or("")),
+                csv_field(r.answer.as_deref().unwrap_or("")),
+                r.iterations.to_string(),
+                csv_field(r.input_path.as_deref().unwrap_or("")),
+                r.input_hash.clone().unwrap_or_default(),
+            ];
+            row.extend(csv_stats_values(r.parse.as_ref()));
+            row.extend(csv_stats_values(r.solve.as_ref()));
+            println!("{}", row.join(","));
+        }
+    }
+
+    // json is a single array, so it can only be written once everything ran.
+    pub fn finish(&mut self) -> anyhow::Result<()> {
+        if self.format == Format::Json {
+            println!("{}", serde_json::to_string_pretty(&self.records)?);
+            self.records.clear();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv_hash() {
+        assert_eq!(hash_input(""), "cbf29ce484222325");
+        assert_eq!(hash_input("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn csv_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn record_json() {
+        let mut record = Record::new(3, 2, "Mull It Over", 1);
+        record.set_status(Status::Error(
+            "line 1, column 1: expected a number".to_string(),
+        ));
+        record.input_hash = Some(hash_input("x"));
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["message"], "line 1, column 1: expected a number");
+        assert_eq!(json["answer"], serde_json::Value::Null);
+        assert_eq!(json["day"], 3);
+    }
+}